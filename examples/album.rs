@@ -1,4 +1,5 @@
 use rspotify::client::SpotifyBuilder;
+use rspotify::model::AlbumId;
 use rspotify::oauth2::CredentialsBuilder;
 
 #[tokio::main]
@@ -34,8 +35,8 @@ async fn main() {
     spotify.request_client_token().await.unwrap();
 
     // Running the requests
-    let birdy_uri = "spotify:album:0sNOF9WDwhWunNAHPD3Baj";
-    let albums = spotify.album(birdy_uri).await;
+    let birdy_uri = AlbumId::from_uri("spotify:album:0sNOF9WDwhWunNAHPD3Baj").unwrap();
+    let albums = spotify.album(&birdy_uri).await;
 
     println!("Response: {:#?}", albums);
 }
@@ -7,6 +7,7 @@
 
 use rspotify::client::SpotifyBuilder;
 use rspotify::oauth2::{CredentialsBuilder, OAuthBuilder};
+use rspotify::scopes::Scopes;
 
 #[tokio::main]
 async fn main() {
@@ -19,15 +20,7 @@ async fn main() {
 
     // Using every possible scope
     let oauth = OAuthBuilder::from_env()
-        .scope(
-            "user-read-email user-read-private user-top-read \
-             user-read-recently-played user-follow-read user-library-read \
-             user-read-currently-playing user-read-playback-state \
-             user-read-playback-position playlist-read-collaborative \
-             playlist-read-private user-follow-modify user-library-modify \
-             user-modify-playback-state playlist-modify-public \
-             playlist-modify-private ugc-image-upload",
-        )
+        .scope(Scopes::all())
         .build()
         .unwrap();
 
@@ -39,7 +32,7 @@ async fn main() {
 
     spotify.prompt_for_user_token().await.unwrap();
 
-    let token = spotify.token.as_ref().unwrap();
+    let token = spotify.token.lock().unwrap().clone().unwrap();
     println!("Access token: {}", &token.access_token);
     println!("Refresh token: {}", token.refresh_token.as_ref().unwrap());
 }
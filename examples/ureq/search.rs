@@ -1,6 +1,7 @@
 use rspotify::client::SpotifyBuilder;
 use rspotify::model::{Country, SearchType};
 use rspotify::oauth2::{CredentialsBuilder, OAuthBuilder};
+use rspotify::scopes::Scope;
 
 fn main() {
     // You can use any logger for debugging.
@@ -30,7 +31,7 @@ fn main() {
     //     .build()
     //     .unwrap();
     let oauth = OAuthBuilder::from_env()
-        .scope("user-read-playback-state")
+        .scope(Scope::UserReadPlaybackState)
         .build()
         .unwrap();
 
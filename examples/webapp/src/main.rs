@@ -16,6 +16,8 @@ use rocket_contrib::templates::Template;
 use rspotify::client::{ClientError, SpotifyBuilder};
 
 use rspotify::oauth2::{CredentialsBuilder, OAuthBuilder, TokenBuilder};
+use rspotify::scopes::Scope;
+use rspotify::store::FileTokenStore;
 use rspotify::util;
 
 use std::collections::HashMap;
@@ -63,7 +65,10 @@ fn init_spotify() -> SpotifyBuilder {
     // (or https). It will fail if you mix them up.
     let oauth = OAuthBuilder::default()
         .redirect_uri("http://localhost:8000/callback")
-        .scope("user-read-currently-playing playlist-modify-private")
+        .scope(vec![
+            Scope::UserReadCurrentlyPlaying,
+            Scope::PlaylistModifyPrivate,
+        ])
         .build()
         .unwrap();
 
@@ -84,7 +89,7 @@ fn init_spotify() -> SpotifyBuilder {
 fn callback(cookies: Cookies, code: String) -> AppResponse {
     let mut spotify = init_spotify();
     let mut spotify = spotify
-        .cache_path(create_cache_path_if_absent(&cookies))
+        .token_store(FileTokenStore::new(create_cache_path_if_absent(&cookies)))
         .build()
         .unwrap();
     return match spotify.request_user_token(code.as_str()) {
@@ -109,7 +114,7 @@ fn index(mut cookies: Cookies) -> AppResponse {
         true => {
             cookies.add(Cookie::new("uuid", util::generate_random_string(64)));
             spotify_builder
-                .cache_path(create_cache_path_if_absent(&cookies))
+                .token_store(FileTokenStore::new(create_cache_path_if_absent(&cookies)))
                 .build()
                 .unwrap()
         }
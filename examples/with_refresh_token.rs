@@ -16,18 +16,20 @@
 //! so in the case of Spotify it doesn't seem to revoke them at all.
 
 use rspotify::client::{Spotify, SpotifyBuilder};
+use rspotify::model::ArtistId;
 use rspotify::oauth2::{CredentialsBuilder, OAuthBuilder};
+use rspotify::scopes::Scope;
 
 // Sample request that will follow some artists, print the user's
 // followed artists, and then unfollow the artists.
 async fn do_things(spotify: Spotify) {
     let artists = vec![
-        "3RGLhK1IP9jnYFH4BRFJBS", // The Clash
-        "0yNLKJebCb8Aueb54LYya3", // New Order
-        "2jzc5TC5TVFLXQlBNiIUzE", // a-ha
+        ArtistId::from_id("3RGLhK1IP9jnYFH4BRFJBS").unwrap(), // The Clash
+        ArtistId::from_id("0yNLKJebCb8Aueb54LYya3").unwrap(), // New Order
+        ArtistId::from_id("2jzc5TC5TVFLXQlBNiIUzE").unwrap(), // a-ha
     ];
     spotify
-        .user_follow_artists(artists.clone())
+        .user_follow_artists(&artists)
         .await
         .expect("couldn't follow artists");
     println!("Followed {} artists successfully.", artists.len());
@@ -43,7 +45,7 @@ async fn do_things(spotify: Spotify) {
     );
 
     spotify
-        .user_unfollow_artists(artists.clone())
+        .user_unfollow_artists(&artists)
         .await
         .expect("couldn't unfollow artists");
     println!("Unfollowed {} artists successfully.", artists.len());
@@ -57,7 +59,7 @@ async fn main() {
     // The default credentials from the `.env` file will be used by default.
     let creds = CredentialsBuilder::from_env().build().unwrap();
     let oauth = OAuthBuilder::from_env()
-        .scope("user-follow-read user-follow-modify")
+        .scope(vec![Scope::UserFollowRead, Scope::UserFollowModify])
         .build()
         .unwrap();
     let mut spotify = SpotifyBuilder::default()
@@ -75,12 +77,13 @@ async fn main() {
         .expect("couldn't authenticate successfully");
     let refresh_token = spotify
         .token
-        .as_ref()
+        .lock()
         .unwrap()
-        .refresh_token
         .as_ref()
         .unwrap()
-        .clone();
+        .refresh_token
+        .clone()
+        .unwrap();
     do_things(spotify).await;
 
     // At a different time, the refresh token can be used to refresh an access
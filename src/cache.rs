@@ -0,0 +1,218 @@
+//! Caching of the responses with an `ETag`, so that the same request is sent
+//! with `If-None-Match` and the cached body is reused when Spotify answers
+//! `304 Not Modified`. The client saves and loads the responses through the
+//! [`ResponseCache`](trait.ResponseCache.html) trait, configured with
+//! [`SpotifyBuilder::response_cache`
+//! ](../client/struct.SpotifyBuilder.html#method.response_cache).
+
+use maybe_async::maybe_async;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use super::client::ClientResult;
+
+/// The number of responses kept by [`MemoryResponseCache::default`
+/// ](struct.MemoryResponseCache.html).
+pub const DEFAULT_CACHE_CAPACITY: usize = 1000;
+
+/// A successful response saved in the cache, along with its `ETag`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub etag: String,
+    pub body: String,
+}
+
+/// Storage for the cached responses. Each response is saved under a key made
+/// of the client's [`Spotify::cache_key`
+/// ](../client/struct.Spotify.html#structfield.cache_key), so that users don't
+/// share their responses, and the URL with its query.
+///
+/// Only the `GET` requests to the API are cached. Errors are logged and
+/// otherwise ignored by the client, which sends the request without the cache
+/// instead.
+#[maybe_async]
+pub trait ResponseCache: Debug + Send + Sync {
+    /// Loads the response saved under the given key, or `None` if there's no
+    /// response for it.
+    async fn get(&self, key: &str) -> ClientResult<Option<CachedResponse>>;
+
+    /// Saves the response under the given key, overwriting the previous one.
+    async fn put(&self, key: &str, response: &CachedResponse) -> ClientResult<()>;
+}
+
+/// Keeps the responses in memory, evicting the least recently used ones when
+/// there are more than its capacity.
+#[derive(Debug)]
+pub struct MemoryResponseCache {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+/// The responses and the order in which they were used, from the least to
+/// the most recent.
+#[derive(Debug, Default)]
+struct LruState {
+    responses: HashMap<String, CachedResponse>,
+    recency: VecDeque<String>,
+}
+
+impl LruState {
+    /// Marks the key as the most recently used one.
+    fn touch(&mut self, key: &str) {
+        if let Some(pos) = self.recency.iter().position(|used| used == key) {
+            self.recency.remove(pos);
+        }
+        self.recency.push_back(key.to_owned());
+    }
+}
+
+impl MemoryResponseCache {
+    /// A cache that keeps at most `capacity` responses.
+    pub fn new(capacity: usize) -> Self {
+        MemoryResponseCache {
+            capacity,
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    /// The number of responses in the cache.
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().responses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for MemoryResponseCache {
+    fn default() -> Self {
+        MemoryResponseCache::new(DEFAULT_CACHE_CAPACITY)
+    }
+}
+
+#[maybe_async]
+impl ResponseCache for MemoryResponseCache {
+    async fn get(&self, key: &str) -> ClientResult<Option<CachedResponse>> {
+        let mut state = self.state.lock().unwrap();
+        let response = state.responses.get(key).cloned();
+        if response.is_some() {
+            state.touch(key);
+        }
+        Ok(response)
+    }
+
+    async fn put(&self, key: &str, response: &CachedResponse) -> ClientResult<()> {
+        let mut state = self.state.lock().unwrap();
+        state.responses.insert(key.to_owned(), response.clone());
+        state.touch(key);
+        while state.responses.len() > self.capacity {
+            match state.recency.pop_front() {
+                Some(oldest) => state.responses.remove(&oldest),
+                None => break,
+            };
+        }
+        Ok(())
+    }
+}
+
+/// Saves each response in a separate JSON file inside a directory, which is
+/// created if it doesn't exist. The files are named after the SHA-256 hash of
+/// their key, and they're never removed.
+#[derive(Debug, Clone)]
+pub struct DirResponseCache {
+    dir: PathBuf,
+}
+
+impl DirResponseCache {
+    pub fn new<T: Into<PathBuf>>(dir: T) -> Self {
+        DirResponseCache { dir: dir.into() }
+    }
+
+    /// The path of the cache file for the given key.
+    pub fn path(&self, key: &str) -> PathBuf {
+        let hash = Sha256::digest(key.as_bytes());
+        let name: String = hash.iter().map(|byte| format!("{:02x}", byte)).collect();
+        self.dir.join(format!("{}.json", name))
+    }
+}
+
+#[maybe_async]
+impl ResponseCache for DirResponseCache {
+    async fn get(&self, key: &str) -> ClientResult<Option<CachedResponse>> {
+        match fs::read_to_string(self.path(key)) {
+            Ok(contents) => Ok(Some(serde_json::from_str(&contents)?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn put(&self, key: &str, response: &CachedResponse) -> ClientResult<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(key), serde_json::to_string(response)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use maybe_async::maybe_async;
+
+    fn response(etag: &str) -> CachedResponse {
+        CachedResponse {
+            etag: etag.to_owned(),
+            body: format!("{{\"etag\": \"{}\"}}", etag),
+        }
+    }
+
+    #[maybe_async]
+    #[cfg_attr(feature = "__async", tokio::test)]
+    #[cfg_attr(feature = "__sync", test)]
+    async fn test_memory_cache() {
+        let cache = MemoryResponseCache::new(2);
+        let cached = cache.get("a").await.unwrap();
+        assert!(cached.is_none());
+
+        cache.put("a", &response("1")).await.unwrap();
+        cache.put("b", &response("2")).await.unwrap();
+        // "a" is used, so "b" is the least recently used one and it's evicted
+        let cached = cache.get("a").await.unwrap();
+        assert_eq!(cached, Some(response("1")));
+        cache.put("c", &response("3")).await.unwrap();
+
+        assert_eq!(cache.len(), 2);
+        let cached = cache.get("b").await.unwrap();
+        assert!(cached.is_none());
+        let cached = cache.get("a").await.unwrap();
+        assert_eq!(cached, Some(response("1")));
+        let cached = cache.get("c").await.unwrap();
+        assert_eq!(cached, Some(response("3")));
+    }
+
+    #[maybe_async]
+    #[cfg_attr(feature = "__async", tokio::test)]
+    #[cfg_attr(feature = "__sync", test)]
+    async fn test_dir_cache() {
+        let dir = std::env::temp_dir().join("rspotify_test_dir_cache");
+        let cache = DirResponseCache::new(&dir);
+        let key = "default https://api.spotify.com/v1/me?market=ES";
+
+        cache.put(key, &response("1")).await.unwrap();
+        assert!(cache.path(key).starts_with(&dir));
+        let cached = cache.get(key).await.unwrap();
+        assert_eq!(cached, Some(response("1")));
+        cache.put(key, &response("2")).await.unwrap();
+        let cached = cache.get(key).await.unwrap();
+        assert_eq!(cached, Some(response("2")));
+        let cached = cache.get("other").await.unwrap();
+        assert!(cached.is_none());
+    }
+}
@@ -4,17 +4,25 @@ use chrono::prelude::*;
 use derive_builder::Builder;
 use log::error;
 use maybe_async::maybe_async;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde_json::map::Map;
 use serde_json::{json, Value};
 use thiserror::Error;
 
-use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use super::http::{BaseClient, Query};
+use super::cache::ResponseCache;
+use super::http::{self, BaseClient, HttpClient, HttpConfig, Query, ResponseMetadata};
 use super::json_insert;
 use super::model::*;
 use super::oauth2::{Credentials, OAuth, Token};
+use super::pagination::{paginate, paginate_cursor, Paginator, DEFAULT_PAGINATION_CHUNKS};
+use super::ratelimit::{RateLimit, RateLimiter};
+use super::retry::RetryPolicy;
+use super::scopes::{Scope, Scopes};
+use super::store::{FileTokenStore, TokenStore};
 
 /// Possible errors returned from the `rspotify` client.
 #[derive(Debug, Error)]
@@ -32,6 +40,10 @@ pub enum ClientError {
     #[error("request error: {0}")]
     Request(String),
 
+    /// The request didn't complete within the configured timeout.
+    #[error("request timed out")]
+    Timeout,
+
     #[error("status code {0}: {1}")]
     StatusCode(u16, String),
 
@@ -53,43 +65,97 @@ pub enum ClientError {
 
     #[error("cache file error: {0}")]
     CacheFile(String),
+
+    /// The redirect after the authorization has an error instead of a code,
+    /// like `access_denied` when the user declined it.
+    #[error("authorization failed: {0}")]
+    AuthorizationFailed(String),
+
+    /// The state in the redirect after the authorization doesn't match
+    /// `OAuth::state`, so it may have been forged.
+    #[error("the state in the authorization redirect doesn't match")]
+    InvalidState,
+
+    /// The access token hasn't been granted the scopes required by the
+    /// endpoint, so Spotify would reject the request.
+    #[error("missing required scopes: {0}")]
+    MissingScopes(Scopes),
+
+    /// The image to upload is larger than `MAX_COVER_IMAGE_SIZE` once
+    /// encoded in base64, so Spotify would reject it.
+    #[error("image too large: {0} bytes encoded in base64")]
+    ImageTooLarge(usize),
+
+    /// Some of the genre seeds passed to `Spotify::recommendations` aren't
+    /// available, which is only checked if `Spotify::validate_genre_seeds` is
+    /// enabled.
+    #[error("unknown genre seeds: {}", .0.join(", "))]
+    UnknownGenreSeeds(Vec<String>),
+
+    /// Spotify answered only some of the chunks of a request split by the
+    /// client without any data, so the results can't be matched with the
+    /// IDs. Contains the position of the first ID in that chunk.
+    #[error("no data in the chunk starting at ID {0}")]
+    EmptyChunk(usize),
 }
 
 pub type ClientResult<T> = Result<T, ClientError>;
 
 /// Matches errors that are returned from the Spotfiy
 /// API as part of the JSON response object.
+///
+/// The player errors are tried first because they're regular errors with an
+/// additional `reason`.
 #[derive(Debug, Error, Deserialize)]
+#[serde(untagged)]
 pub enum APIError {
-    /// See https://developer.spotify.com/documentation/web-api/reference/object-model/#error-object
-    #[error("{status}: {message}")]
-    #[serde(alias = "error")]
-    Regular { status: u16, message: String },
-
     /// See https://developer.spotify.com/documentation/web-api/reference/object-model/#player-error-object
     #[error("{status} ({reason}): {message}")]
-    #[serde(alias = "error")]
     Player {
         status: u16,
         message: String,
-        reason: String,
+        reason: PlayerErrorReason,
     },
+
+    /// See https://developer.spotify.com/documentation/web-api/reference/object-model/#error-object
+    #[error("{status}: {message}")]
+    Regular { status: u16, message: String },
 }
 
 pub const DEFAULT_API_PREFIX: &str = "https://api.spotify.com/v1/";
+pub const DEFAULT_AUTH_PREFIX: &str = "https://accounts.spotify.com/";
 pub const DEFAULT_CACHE_PATH: &str = ".spotify_token_cache.json";
+pub const DEFAULT_CACHE_KEY: &str = "default";
+/// The maximum size of a playlist cover image, encoded in base64, in bytes.
+pub const MAX_COVER_IMAGE_SIZE: usize = 256 * 1024;
 
 /// Spotify API object
 #[derive(Builder, Debug, Clone)]
 pub struct Spotify {
-    /// reqwest needs an instance of its client to perform requests.
-    #[cfg(feature = "client-reqwest")]
-    #[builder(setter(skip))]
-    pub(in crate) client: reqwest::Client,
+    /// The HTTP client that sends the requests. By default it's the one
+    /// included for the enabled feature, like [`ReqwestClient`
+    /// ](../http/struct.ReqwestClient.html) for `client-reqwest`, configured
+    /// with `http_config`.
+    #[builder(setter(custom), default = "self.default_http_client()?")]
+    pub http: Arc<dyn HttpClient>,
+
+    /// The timeouts, user agent and proxy of the default HTTP client. See
+    /// [`HttpConfig`](../http/struct.HttpConfig.html) for the defaults.
+    #[builder(default)]
+    pub http_config: HttpConfig,
+
+    /// The maximum duration of each request, which unlike the one in
+    /// `http_config` also applies to custom HTTP clients. It can be changed
+    /// for specific calls with [`Spotify::with_timeout`
+    /// ](struct.Spotify.html#method.with_timeout). No limit by default.
+    #[builder(setter(strip_option), default)]
+    pub timeout: Option<Duration>,
 
     /// The access token information required for requests to the Spotify API.
-    #[builder(setter(strip_option), default)]
-    pub token: Option<Token>,
+    /// It's shared between clones of the client so that it can be refreshed
+    /// automatically from any of them.
+    #[builder(setter(custom), default)]
+    pub token: Arc<Mutex<Option<Token>>>,
 
     /// The credentials needed for obtaining a new access token, for requests.
     /// without OAuth authentication.
@@ -107,38 +173,284 @@ pub struct Spotify {
     #[builder(setter(into), default = "String::from(DEFAULT_API_PREFIX)")]
     pub prefix: String,
 
-    /// The cache file path, in case it's used. By default it's
-    /// [`DEFAULT_CACHE_PATH`](constant.DEFAULT_API_PREFIX.html).
-    #[builder(default = r#"PathBuf::from(DEFAULT_CACHE_PATH)"#)]
-    pub cache_path: PathBuf,
+    /// The Spotify Accounts service prefix, used for the authorization and
+    /// token endpoints, [`DEFAULT_AUTH_PREFIX`
+    /// ](constant.DEFAULT_AUTH_PREFIX.html) by default.
+    #[builder(setter(into), default = "String::from(DEFAULT_AUTH_PREFIX)")]
+    pub auth_prefix: String,
+
+    /// Where the token is saved, in case it's used. By default it's a
+    /// [`FileTokenStore`](../store/struct.FileTokenStore.html) at
+    /// [`DEFAULT_CACHE_PATH`](constant.DEFAULT_CACHE_PATH.html).
+    #[builder(
+        setter(custom),
+        default = "Arc::new(FileTokenStore::new(DEFAULT_CACHE_PATH))"
+    )]
+    pub token_store: Arc<dyn TokenStore>,
+
+    /// The key the token is saved under in the token store, like the user's
+    /// ID in multi-tenant applications. By default it's
+    /// [`DEFAULT_CACHE_KEY`](constant.DEFAULT_CACHE_KEY.html).
+    #[builder(setter(into), default = "String::from(DEFAULT_CACHE_KEY)")]
+    pub cache_key: String,
+
+    /// Whether the access token should be refreshed automatically when it
+    /// expires or when Spotify rejects it. Enabled by default.
+    #[builder(default = "true")]
+    pub token_refreshing: bool,
+
+    /// How the requests that fail temporarily are retried, like when Spotify
+    /// rate limits the client. See [`RetryPolicy`
+    /// ](../retry/struct.RetryPolicy.html) for the defaults.
+    #[builder(default)]
+    pub retry_policy: RetryPolicy,
+
+    /// Throttles the requests to stay under Spotify's quotas, shared between
+    /// the clones of the client. See [`RateLimit`
+    /// ](../ratelimit/struct.RateLimit.html). Disabled by default.
+    #[builder(setter(custom), default)]
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+
+    /// Whether the chunks of the endpoints that take more IDs than Spotify
+    /// allows per call, like [`Spotify::tracks`](#method.tracks), are
+    /// requested concurrently. Only the asynchronous clients support it, and
+    /// the rate limiter still applies. Disabled by default.
+    #[builder(default)]
+    pub concurrent_chunks: bool,
+
+    /// Whether the genre seeds passed to [`Spotify::recommendations`
+    /// ](#method.recommendations) are checked against the available ones
+    /// before the request, failing with `ClientError::UnknownGenreSeeds`. This
+    /// takes an additional request for the available genres each time.
+    /// Disabled by default.
+    #[builder(default)]
+    pub validate_genre_seeds: bool,
+
+    /// Where the responses with an `ETag` are cached, so that they're
+    /// requested again with `If-None-Match`. See the [`cache`
+    /// ](../cache/index.html) module. Disabled by default.
+    #[builder(setter(custom), default)]
+    pub response_cache: Option<Arc<dyn ResponseCache>>,
+
+    /// Where the metadata of the last response is saved, only when requested
+    /// with `Spotify::with_response_metadata`.
+    #[builder(setter(skip))]
+    pub(crate) last_response: Option<Arc<Mutex<Option<ResponseMetadata>>>>,
+}
+
+impl SpotifyBuilder {
+    /// Sets the access token information required for requests to the
+    /// Spotify API.
+    pub fn token(&mut self, token: Token) -> &mut Self {
+        self.token = Some(Arc::new(Mutex::new(Some(token))));
+        self
+    }
+
+    /// Sets the HTTP client that sends the requests, which can be any
+    /// implementation of [`HttpClient`](../http/trait.HttpClient.html).
+    pub fn http_client<T: HttpClient + 'static>(&mut self, client: T) -> &mut Self {
+        self.http = Some(Arc::new(client));
+        self
+    }
+
+    /// Sets a preconfigured [`reqwest::Client`
+    /// ](https://docs.rs/reqwest/0.10/reqwest/struct.Client.html) for the
+    /// requests, like one shared with the rest of the application. Then
+    /// `http_config` is ignored.
+    #[cfg(feature = "client-reqwest")]
+    pub fn reqwest_client(&mut self, client: reqwest::Client) -> &mut Self {
+        self.http_client(http::ReqwestClient::from(client))
+    }
+
+    /// Sets a preconfigured [`ureq::Agent`
+    /// ](https://docs.rs/ureq/1/ureq/struct.Agent.html) for the requests,
+    /// like one shared with the rest of the application. Then `http_config`
+    /// is ignored.
+    #[cfg(feature = "client-ureq")]
+    pub fn ureq_agent(&mut self, agent: ureq::Agent) -> &mut Self {
+        self.http_client(http::UreqClient::from(agent))
+    }
+
+    /// The default HTTP client, configured with `http_config`.
+    fn default_http_client(&self) -> Result<Arc<dyn HttpClient>, String> {
+        let config = self.http_config.clone().unwrap_or_default();
+        http::default_client(&config).map_err(|err| err.to_string())
+    }
+
+    /// Sets where the token is saved, in case it's used.
+    pub fn token_store<T: TokenStore + 'static>(&mut self, store: T) -> &mut Self {
+        self.token_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Sets how the requests are throttled.
+    pub fn rate_limit(&mut self, limit: RateLimit) -> &mut Self {
+        self.rate_limiter = Some(Some(Arc::new(RateLimiter::new(limit))));
+        self
+    }
+
+    /// Sets where the responses are cached, like a [`MemoryResponseCache`
+    /// ](../cache/struct.MemoryResponseCache.html).
+    pub fn response_cache<T: ResponseCache + 'static>(&mut self, cache: T) -> &mut Self {
+        self.response_cache = Some(Some(Arc::new(cache)));
+        self
+    }
+}
+
+/// Defines the paginated version of an endpoint, named `$stream` when the
+/// client is asynchronous and `$iter` when it's synchronous. Their bodies are
+/// the same because `paginate` and `Paginator` are also chosen by the client.
+macro_rules! paginated {
+    (
+        $(#[$attr:meta])*
+        pub fn $stream:ident | $iter:ident <$lt:lifetime> (
+            &$self:ident $(, $arg:ident: $ty:ty)* $(,)?
+        ) -> $item:ty $body:block
+    ) => {
+        $(#[$attr])*
+        #[cfg(feature = "__async")]
+        pub fn $stream<$lt>(&$lt $self $(, $arg: $ty)*) -> Paginator<$lt, $item> $body
+
+        $(#[$attr])*
+        #[cfg(feature = "__sync")]
+        pub fn $iter<$lt>(&$lt $self $(, $arg: $ty)*) -> Paginator<$lt, $item> $body
+    };
+}
+
+/// Joins a list of IDs with commas, as expected by the endpoints that take
+/// multiple items at once.
+fn join_ids<'a, T: 'a + Id>(ids: impl IntoIterator<Item = &'a T>) -> String {
+    ids.into_iter().map(Id::id).collect::<Vec<_>>().join(",")
+}
+
+/// Splits a list of items in chunks of at most `size`, since the endpoints
+/// that take multiple items at once have a limit per call. An empty list is
+/// still a single chunk, so that the request is sent as usual.
+fn chunks<T>(items: &[T], size: usize) -> Vec<&[T]> {
+    if items.is_empty() {
+        vec![items]
+    } else {
+        items.chunks(size).collect()
+    }
+}
+
+/// Splits a list of IDs in chunks of at most `size`, each joined with commas
+/// as expected by the endpoints that take multiple items at once.
+fn chunk_ids<'a, T: 'a + Id>(ids: impl IntoIterator<Item = &'a T>, size: usize) -> Vec<String> {
+    let ids: Vec<&str> = ids.into_iter().map(Id::id).collect();
+    chunks(&ids, size)
+        .into_iter()
+        .map(|chunk| chunk.join(","))
+        .collect()
+}
+
+/// The scopes that allow modifying a playlist, of which any is enough when it
+/// isn't known whether the playlist is public.
+const PLAYLIST_MODIFY_SCOPES: [Scope; 2] =
+    [Scope::PlaylistModifyPublic, Scope::PlaylistModifyPrivate];
+
+/// The scope needed to modify a playlist, which depends on whether it's
+/// public.
+fn playlist_modify_scope(public: bool) -> Scope {
+    if public {
+        Scope::PlaylistModifyPublic
+    } else {
+        Scope::PlaylistModifyPrivate
+    }
 }
 
 // Endpoint-related methods for the client.
 impl Spotify {
-    /// Returns the access token, or an error in case it's not configured.
-    pub(in crate) fn get_token(&self) -> ClientResult<&Token> {
+    /// Returns a copy of the access token, or an error in case it's not
+    /// configured.
+    pub(crate) fn get_token(&self) -> ClientResult<Token> {
         self.token
-            .as_ref()
+            .lock()
+            .unwrap()
+            .clone()
             .ok_or_else(|| ClientError::InvalidAuth("no access token configured".to_string()))
     }
 
     /// Returns the credentials, or an error in case it's not configured.
-    pub(in crate) fn get_creds(&self) -> ClientResult<&Credentials> {
+    pub(crate) fn get_creds(&self) -> ClientResult<&Credentials> {
         self.credentials
             .as_ref()
             .ok_or_else(|| ClientError::InvalidAuth("no credentials configured".to_string()))
     }
 
     /// Returns the oauth information, or an error in case it's not configured.
-    pub(in crate) fn get_oauth(&self) -> ClientResult<&OAuth> {
+    pub(crate) fn get_oauth(&self) -> ClientResult<&OAuth> {
         self.oauth
             .as_ref()
             .ok_or_else(|| ClientError::InvalidAuth("no oauth configured".to_string()))
     }
 
-    /// TODO: should be moved into a custom type
-    fn get_uri(&self, _type: Type, _id: &str) -> String {
-        format!("spotify:{}:{}", _type.to_string(), self.get_id(_type, _id))
+    /// Returns a copy of the client whose requests fail with
+    /// `ClientError::Timeout` after the given duration, for specific calls
+    /// that shouldn't wait as long as the rest, like
+    /// `spotify.with_timeout(Duration::from_secs(5)).track(&id)`. The token is
+    /// still shared with the original client.
+    pub fn with_timeout(&self, timeout: Duration) -> Spotify {
+        Spotify {
+            timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of the client that records the status and headers of
+    /// the responses to its requests, available with
+    /// [`Spotify::last_response`](#method.last_response) after calling an
+    /// endpoint. Each copy has its own record, so concurrent tasks should use
+    /// one each. The token is still shared with the original client.
+    pub fn with_response_metadata(&self) -> Spotify {
+        Spotify {
+            last_response: Some(Arc::new(Mutex::new(None))),
+            ..self.clone()
+        }
+    }
+
+    /// The status and headers of the last response received, whether it was
+    /// successful or not, if the client was obtained with
+    /// [`Spotify::with_response_metadata`](#method.with_response_metadata).
+    pub fn last_response(&self) -> Option<ResponseMetadata> {
+        self.last_response.as_ref()?.lock().unwrap().clone()
+    }
+
+    /// Checks that the access token has been granted the scopes required by
+    /// an endpoint, so that it fails early with `ClientError::MissingScopes`
+    /// instead of being rejected by Spotify. Tokens without any scopes, like
+    /// the ones obtained with the client credentials flow or built from a bare
+    /// access token, aren't checked.
+    fn require_scopes(&self, required: &[Scope]) -> ClientResult<()> {
+        let token = self.token.lock().unwrap();
+        let granted = match token.as_ref() {
+            Some(tok) if !tok.scope.is_empty() => &tok.scope,
+            _ => return Ok(()),
+        };
+
+        let missing = Scopes::from(required.to_vec()).difference(granted);
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(ClientError::MissingScopes(missing))
+        }
+    }
+
+    /// Same as `require_scopes`, but for endpoints that accept any of the
+    /// given scopes. If none of them has been granted, all of them are
+    /// returned in `ClientError::MissingScopes`.
+    fn require_any_scope(&self, any_of: &[Scope]) -> ClientResult<()> {
+        let token = self.token.lock().unwrap();
+        let granted = match token.as_ref() {
+            Some(tok) if !tok.scope.is_empty() => &tok.scope,
+            _ => return Ok(()),
+        };
+
+        if any_of.iter().any(|scope| granted.contains(*scope)) {
+            Ok(())
+        } else {
+            Err(ClientError::MissingScopes(Scopes::from(any_of.to_vec())))
+        }
     }
 
     /// Converts a JSON response from Spotify into its model.
@@ -146,39 +458,64 @@ impl Spotify {
         serde_json::from_str::<T>(input).map_err(Into::into)
     }
 
-    /// Get spotify id by type and id
-    /// TODO: should be rewritten and moved into a separate type for IDs
-    fn get_id(&self, _type: Type, id: &str) -> String {
-        let mut _id = id.to_owned();
-        let fields: Vec<&str> = _id.split(':').collect();
-        let len = fields.len();
-        if len >= 3 {
-            if _type.to_string() != fields[len - 2] {
-                error!(
-                    "expected id of type {:?} but found type {:?} {:?}",
-                    _type,
-                    fields[len - 2],
-                    _id
-                );
-            } else {
-                return fields[len - 1].to_owned();
-            }
+    /// Converts the JSON lists in the responses to the chunks of a request,
+    /// concatenating them in order.
+    fn concat_results<T: DeserializeOwned>(&self, results: &[String]) -> ClientResult<Vec<T>> {
+        let mut items = Vec::new();
+        for result in results {
+            items.extend(self.convert_result::<Vec<T>>(result)?);
         }
-        let sfields: Vec<&str> = _id.split('/').collect();
-        let len: usize = sfields.len();
-        if len >= 3 {
-            if _type.to_string() != sfields[len - 2] {
-                error!(
-                    "expected id of type {:?} but found type {:?} {:?}",
-                    _type,
-                    sfields[len - 2],
-                    _id
-                );
-            } else {
-                return sfields[len - 1].to_owned();
+        Ok(items)
+    }
+
+    /// Sends a GET request for each chunk of IDs, passed in the `ids`
+    /// parameter, returning the responses in the same order. The chunks are
+    /// requested concurrently if `concurrent_chunks` is enabled.
+    #[cfg(feature = "__async")]
+    async fn get_chunks(
+        &self,
+        url: &str,
+        chunks: Vec<String>,
+        query: &Query,
+    ) -> ClientResult<Vec<String>> {
+        let queries: Vec<Query> = chunks
+            .into_iter()
+            .map(|ids| {
+                let mut query = query.clone();
+                query.insert("ids".to_owned(), ids);
+                query
+            })
+            .collect();
+
+        if self.concurrent_chunks {
+            let requests = queries.iter().map(|query| self.get(url, None, query));
+            futures::future::try_join_all(requests).await
+        } else {
+            let mut results = Vec::with_capacity(queries.len());
+            for query in &queries {
+                results.push(self.get(url, None, query).await?);
             }
+            Ok(results)
         }
-        _id.to_owned()
+    }
+
+    /// Sends a GET request for each chunk of IDs, passed in the `ids`
+    /// parameter, returning the responses in the same order.
+    #[cfg(feature = "__sync")]
+    fn get_chunks(
+        &self,
+        url: &str,
+        chunks: Vec<String>,
+        query: &Query,
+    ) -> ClientResult<Vec<String>> {
+        chunks
+            .into_iter()
+            .map(|ids| {
+                let mut query = query.clone();
+                query.insert("ids".to_owned(), ids);
+                self.get(url, None, &query)
+            })
+            .collect()
     }
 
     /// Append device ID to an API path.
@@ -194,89 +531,87 @@ impl Spotify {
         new_path
     }
 
-    /// Returns a single track given the track's ID, URI or URL.
+    /// Returns a single track given the track's ID.
     ///
     /// Parameters:
-    /// - track_id - a spotify URI, URL or ID
+    /// - track_id - a track ID
     ///
     /// [Reference](https://developer.spotify.com/web-api/get-track/)
     #[maybe_async]
-    pub async fn track(&self, track_id: &str) -> ClientResult<FullTrack> {
-        let trid = self.get_id(Type::Track, track_id);
-        let url = format!("tracks/{}", trid);
+    pub async fn track(&self, track_id: &TrackId) -> ClientResult<FullTrack> {
+        let url = format!("tracks/{}", track_id.id());
         let result = self.get(&url, None, &Query::new()).await?;
         self.convert_result(&result)
     }
 
-    /// Returns a list of tracks given a list of track IDs, URIs, or URLs.
+    /// Returns a list of tracks given a list of track IDs, requested in chunks
+    /// of 50.
     ///
     /// Parameters:
-    /// - track_ids - a list of spotify URIs, URLs or IDs
+    /// - track_ids - a list of track IDs
     /// - market - an ISO 3166-1 alpha-2 country code.
     ///
     /// [Reference](https://developer.spotify.com/web-api/get-several-tracks/)
     #[maybe_async]
     pub async fn tracks<'a>(
         &self,
-        track_ids: impl IntoIterator<Item = &'a str>,
+        track_ids: impl IntoIterator<Item = &'a TrackId>,
         market: Option<Country>,
     ) -> ClientResult<Vec<FullTrack>> {
-        // TODO: this can be improved
-        let mut ids: Vec<String> = vec![];
-        for track_id in track_ids {
-            ids.push(self.get_id(Type::Track, track_id));
-        }
-
         let mut params = Query::new();
         if let Some(market) = market {
             params.insert("market".to_owned(), market.to_string());
         }
 
-        let url = format!("tracks/?ids={}", ids.join(","));
-        let result = self.get(&url, None, &params).await?;
-        self.convert_result::<FullTracks>(&result).map(|x| x.tracks)
+        let results = self
+            .get_chunks("tracks/", chunk_ids(track_ids, 50), &params)
+            .await?;
+        let mut tracks = Vec::new();
+        for result in results {
+            tracks.extend(self.convert_result::<FullTracks>(&result)?.tracks);
+        }
+        Ok(tracks)
     }
 
-    /// Returns a single artist given the artist's ID, URI or URL.
+    /// Returns a single artist given the artist's ID.
     ///
     /// Parameters:
-    /// - artist_id - an artist ID, URI or URL
+    /// - artist_id - an artist ID
     ///
     /// [Reference](https://developer.spotify.com/web-api/get-artist/)
     #[maybe_async]
-    pub async fn artist(&self, artist_id: &str) -> ClientResult<FullArtist> {
-        let trid = self.get_id(Type::Artist, artist_id);
-        let url = format!("artists/{}", trid);
+    pub async fn artist(&self, artist_id: &ArtistId) -> ClientResult<FullArtist> {
+        let url = format!("artists/{}", artist_id.id());
         let result = self.get(&url, None, &Query::new()).await?;
         self.convert_result(&result)
     }
 
-    /// Returns a list of artists given the artist IDs, URIs, or URLs.
+    /// Returns a list of artists given the artist IDs, requested in chunks of
+    /// 50.
     ///
     /// Parameters:
-    /// - artist_ids - a list of artist IDs, URIs or URLs
+    /// - artist_ids - a list of artist IDs
     ///
     /// [Reference](https://developer.spotify.com/web-api/get-several-artists/)
     #[maybe_async]
     pub async fn artists<'a>(
         &self,
-        artist_ids: impl IntoIterator<Item = &'a str>,
+        artist_ids: impl IntoIterator<Item = &'a ArtistId>,
     ) -> ClientResult<Vec<FullArtist>> {
-        let mut ids: Vec<String> = vec![];
-        for artist_id in artist_ids {
-            ids.push(self.get_id(Type::Artist, artist_id));
+        let results = self
+            .get_chunks("artists/", chunk_ids(artist_ids, 50), &Query::new())
+            .await?;
+        let mut artists = Vec::new();
+        for result in results {
+            artists.extend(self.convert_result::<FullArtists>(&result)?.artists);
         }
-        let url = format!("artists/?ids={}", ids.join(","));
-        let result = self.get(&url, None, &Query::new()).await?;
-
-        self.convert_result::<FullArtists>(&result)
-            .map(|x| x.artists)
+        Ok(artists)
     }
 
     /// Get Spotify catalog information about an artist's albums.
     ///
     /// Parameters:
-    /// - artist_id - the artist ID, URI or URL
+    /// - artist_id - the artist ID
     /// - album_type - 'album', 'single', 'appears_on', 'compilation'
     /// - country - limit the response to one particular country.
     /// - limit  - the number of albums to return
@@ -286,7 +621,7 @@ impl Spotify {
     #[maybe_async]
     pub async fn artist_albums(
         &self,
-        artist_id: &str,
+        artist_id: &ArtistId,
         album_type: Option<AlbumType>,
         country: Option<Country>,
         limit: Option<u32>,
@@ -305,24 +640,41 @@ impl Spotify {
         if let Some(country) = country {
             params.insert("country".to_owned(), country.to_string());
         }
-        let trid = self.get_id(Type::Artist, artist_id);
-        let url = format!("artists/{}/albums", trid);
+        let url = format!("artists/{}/albums", artist_id.id());
         let result = self.get(&url, None, &params).await?;
         self.convert_result(&result)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::artist_albums`
+        /// ](#method.artist_albums), which returns all of the artist's albums.
+        pub fn artist_albums_stream | artist_albums_iter<'a>(
+            &self,
+            artist_id: &'a ArtistId,
+            album_type: Option<AlbumType>,
+            country: Option<Country>,
+        ) -> SimplifiedAlbum {
+            paginate(
+                move |limit, offset| {
+                    self.artist_albums(artist_id, album_type, country, Some(limit), Some(offset))
+                },
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Get Spotify catalog information about an artist's top 10 tracks by
     /// country.
     ///
     /// Parameters:
-    /// - artist_id - the artist ID, URI or URL
+    /// - artist_id - the artist ID
     /// - country - limit the response to one particular country.
     ///
     /// [Reference](https://developer.spotify.com/web-api/get-artists-top-tracks/)
     #[maybe_async]
     pub async fn artist_top_tracks<T: Into<Option<Country>>>(
         &self,
-        artist_id: &str,
+        artist_id: &ArtistId,
         country: T,
     ) -> ClientResult<Vec<FullTrack>> {
         let mut params = Query::with_capacity(1);
@@ -331,8 +683,7 @@ impl Spotify {
             country.into().unwrap_or(Country::UnitedStates).to_string(),
         );
 
-        let trid = self.get_id(Type::Artist, artist_id);
-        let url = format!("artists/{}/top-tracks", trid);
+        let url = format!("artists/{}/top-tracks", artist_id.id());
         let result = self.get(&url, None, &params).await?;
         self.convert_result::<FullTracks>(&result).map(|x| x.tracks)
     }
@@ -342,51 +693,53 @@ impl Spotify {
     /// listening history.
     ///
     /// Parameters:
-    /// - artist_id - the artist ID, URI or URL
+    /// - artist_id - the artist ID
     ///
     /// [Reference](https://developer.spotify.com/web-api/get-related-artists/)
     #[maybe_async]
-    pub async fn artist_related_artists(&self, artist_id: &str) -> ClientResult<Vec<FullArtist>> {
-        let trid = self.get_id(Type::Artist, artist_id);
-        let url = format!("artists/{}/related-artists", trid);
+    pub async fn artist_related_artists(
+        &self,
+        artist_id: &ArtistId,
+    ) -> ClientResult<Vec<FullArtist>> {
+        let url = format!("artists/{}/related-artists", artist_id.id());
         let result = self.get(&url, None, &Query::new()).await?;
         self.convert_result::<FullArtists>(&result)
             .map(|x| x.artists)
     }
 
-    /// Returns a single album given the album's ID, URIs or URL.
+    /// Returns a single album given the album's ID.
     ///
     /// Parameters:
-    /// - album_id - the album ID, URI or URL
+    /// - album_id - the album ID
     ///
     /// [Reference](https://developer.spotify.com/web-api/get-album/)
     #[maybe_async]
-    pub async fn album(&self, album_id: &str) -> ClientResult<FullAlbum> {
-        let trid = self.get_id(Type::Album, album_id);
-        let url = format!("albums/{}", trid);
+    pub async fn album(&self, album_id: &AlbumId) -> ClientResult<FullAlbum> {
+        let url = format!("albums/{}", album_id.id());
 
         let result = self.get(&url, None, &Query::new()).await?;
         self.convert_result(&result)
     }
 
-    /// Returns a list of albums given the album IDs, URIs, or URLs.
+    /// Returns a list of albums given the album IDs.
     ///
     /// Parameters:
-    /// - albums_ids - a list of album IDs, URIs or URLs
+    /// - albums_ids - a list of album IDs
     ///
     /// [Reference](https://developer.spotify.com/web-api/get-several-albums/)
     #[maybe_async]
     pub async fn albums<'a>(
         &self,
-        album_ids: impl IntoIterator<Item = &'a str>,
+        album_ids: impl IntoIterator<Item = &'a AlbumId>,
     ) -> ClientResult<Vec<FullAlbum>> {
-        let mut ids: Vec<String> = vec![];
-        for album_id in album_ids {
-            ids.push(self.get_id(Type::Album, album_id));
+        let results = self
+            .get_chunks("albums/", chunk_ids(album_ids, 20), &Query::new())
+            .await?;
+        let mut albums = Vec::new();
+        for result in results {
+            albums.extend(self.convert_result::<FullAlbums>(&result)?.albums);
         }
-        let url = format!("albums/?ids={}", ids.join(","));
-        let result = self.get(&url, None, &Query::new()).await?;
-        self.convert_result::<FullAlbums>(&result).map(|x| x.albums)
+        Ok(albums)
     }
 
     /// Search for an Item. Get Spotify catalog information about artists,
@@ -433,7 +786,7 @@ impl Spotify {
     /// Get Spotify catalog information about an album's tracks.
     ///
     /// Parameters:
-    /// - album_id - the album ID, URI or URL
+    /// - album_id - the album ID
     /// - limit  - the number of items to return
     /// - offset - the index of the first item to return
     ///
@@ -441,19 +794,32 @@ impl Spotify {
     #[maybe_async]
     pub async fn album_track<L: Into<Option<u32>>, O: Into<Option<u32>>>(
         &self,
-        album_id: &str,
+        album_id: &AlbumId,
         limit: L,
         offset: O,
     ) -> ClientResult<Page<SimplifiedTrack>> {
         let mut params = Query::with_capacity(2);
         params.insert("limit".to_owned(), limit.into().unwrap_or(50).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
-        let trid = self.get_id(Type::Album, album_id);
-        let url = format!("albums/{}/tracks", trid);
+        let url = format!("albums/{}/tracks", album_id.id());
         let result = self.get(&url, None, &params).await?;
         self.convert_result(&result)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::album_track`
+        /// ](#method.album_track), which returns all of the album's tracks.
+        pub fn album_track_stream | album_track_iter<'a>(
+            &self,
+            album_id: &'a AlbumId,
+        ) -> SimplifiedTrack {
+            paginate(
+                move |limit, offset| self.album_track(album_id, limit, offset),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Gets basic profile information about a Spotify User.
     ///
     /// Parameters:
@@ -461,8 +827,8 @@ impl Spotify {
     ///
     /// [Reference](https://developer.spotify.com/web-api/get-users-profile/)
     #[maybe_async]
-    pub async fn user(&self, user_id: &str) -> ClientResult<PublicUser> {
-        let url = format!("users/{}", user_id);
+    pub async fn user(&self, user_id: &UserId) -> ClientResult<PublicUser> {
+        let url = format!("users/{}", user_id.id());
         let result = self.get(&url, None, &Query::new()).await?;
         self.convert_result(&result)
     }
@@ -477,7 +843,7 @@ impl Spotify {
     #[maybe_async]
     pub async fn playlist(
         &self,
-        playlist_id: &str,
+        playlist_id: &PlaylistId,
         fields: Option<&str>,
         market: Option<Country>,
     ) -> ClientResult<FullPlaylist> {
@@ -489,8 +855,7 @@ impl Spotify {
             params.insert("market".to_owned(), market.to_string());
         }
 
-        let plid = self.get_id(Type::Playlist, playlist_id);
-        let url = format!("playlists/{}", plid);
+        let url = format!("playlists/{}", playlist_id.id());
         let result = self.get(&url, None, &params).await?;
         self.convert_result(&result)
     }
@@ -508,6 +873,7 @@ impl Spotify {
         limit: L,
         offset: O,
     ) -> ClientResult<Page<SimplifiedPlaylist>> {
+        self.require_any_scope(&[Scope::PlaylistReadPrivate, Scope::PlaylistReadCollaborative])?;
         let mut params = Query::with_capacity(2);
         params.insert("limit".to_owned(), limit.into().unwrap_or(50).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
@@ -516,6 +882,20 @@ impl Spotify {
         self.convert_result(&result)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::current_user_playlists`
+        /// ](#method.current_user_playlists), which returns all of the current
+        /// user's playlists.
+        pub fn current_user_playlists_stream | current_user_playlists_iter<'a>(
+            &self,
+        ) -> SimplifiedPlaylist {
+            paginate(
+                move |limit, offset| self.current_user_playlists(limit, offset),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Gets playlists of a user.
     ///
     /// Parameters:
@@ -527,18 +907,33 @@ impl Spotify {
     #[maybe_async]
     pub async fn user_playlists<L: Into<Option<u32>>, O: Into<Option<u32>>>(
         &self,
-        user_id: &str,
+        user_id: &UserId,
         limit: L,
         offset: O,
     ) -> ClientResult<Page<SimplifiedPlaylist>> {
         let mut params = Query::with_capacity(2);
         params.insert("limit".to_owned(), limit.into().unwrap_or(50).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
-        let url = format!("users/{}/playlists", user_id);
+        let url = format!("users/{}/playlists", user_id.id());
         let result = self.get(&url, None, &params).await?;
         self.convert_result(&result)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::user_playlists`
+        /// ](#method.user_playlists), which returns all of the user's
+        /// playlists.
+        pub fn user_playlists_stream | user_playlists_iter<'a>(
+            &self,
+            user_id: &'a UserId,
+        ) -> SimplifiedPlaylist {
+            paginate(
+                move |limit, offset| self.user_playlists(user_id, limit, offset),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Gets playlist of a user.
     ///
     /// Parameters:
@@ -550,8 +945,8 @@ impl Spotify {
     #[maybe_async]
     pub async fn user_playlist(
         &self,
-        user_id: &str,
-        playlist_id: Option<&mut str>,
+        user_id: &UserId,
+        playlist_id: Option<&PlaylistId>,
         fields: Option<&str>,
         market: Option<Country>,
     ) -> ClientResult<FullPlaylist> {
@@ -564,13 +959,12 @@ impl Spotify {
         }
         match playlist_id {
             Some(playlist_id) => {
-                let plid = self.get_id(Type::Playlist, playlist_id);
-                let url = format!("users/{}/playlists/{}", user_id, plid);
+                let url = format!("users/{}/playlists/{}", user_id.id(), playlist_id.id());
                 let result = self.get(&url, None, &params).await?;
                 self.convert_result(&result)
             }
             None => {
-                let url = format!("users/{}/starred", user_id);
+                let url = format!("users/{}/starred", user_id.id());
                 let result = self.get(&url, None, &params).await?;
                 self.convert_result(&result)
             }
@@ -590,7 +984,7 @@ impl Spotify {
     #[maybe_async]
     pub async fn playlist_tracks<L: Into<Option<u32>>, O: Into<Option<u32>>>(
         &self,
-        playlist_id: &str,
+        playlist_id: &PlaylistId,
         fields: Option<&str>,
         limit: L,
         offset: O,
@@ -605,12 +999,30 @@ impl Spotify {
         if let Some(fields) = fields {
             params.insert("fields".to_owned(), fields.to_owned());
         }
-        let plid = self.get_id(Type::Playlist, playlist_id);
-        let url = format!("playlists/{}/tracks", plid);
+        let url = format!("playlists/{}/tracks", playlist_id.id());
         let result = self.get(&url, None, &params).await?;
         self.convert_result(&result)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::playlist_tracks`
+        /// ](#method.playlist_tracks), which returns all of the playlist's
+        /// items.
+        pub fn playlist_tracks_stream | playlist_tracks_iter<'a>(
+            &self,
+            playlist_id: &'a PlaylistId,
+            fields: Option<&'a str>,
+            market: Option<Country>,
+        ) -> PlaylistItem {
+            paginate(
+                move |limit, offset| {
+                    self.playlist_tracks(playlist_id, fields, limit, offset, market)
+                },
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Creates a playlist for a user.
     ///
     /// Parameters:
@@ -623,19 +1035,20 @@ impl Spotify {
     #[maybe_async]
     pub async fn user_playlist_create<P: Into<Option<bool>>, D: Into<Option<String>>>(
         &self,
-        user_id: &str,
+        user_id: &UserId,
         name: &str,
         public: P,
         description: D,
     ) -> ClientResult<FullPlaylist> {
         let public = public.into().unwrap_or(true);
+        self.require_scopes(&[playlist_modify_scope(public)])?;
         let description = description.into().unwrap_or_else(|| "".to_owned());
         let params = json!({
             "name": name,
             "public": public,
             "description": description
         });
-        let url = format!("users/{}/playlists", user_id);
+        let url = format!("users/{}/playlists", user_id.id());
         let result = self.post(&url, None, &params).await?;
         self.convert_result(&result)
     }
@@ -653,12 +1066,13 @@ impl Spotify {
     #[maybe_async]
     pub async fn playlist_change_detail(
         &self,
-        playlist_id: &str,
+        playlist_id: &PlaylistId,
         name: Option<&str>,
         public: Option<bool>,
         description: Option<String>,
         collaborative: Option<bool>,
     ) -> ClientResult<String> {
+        self.require_any_scope(&PLAYLIST_MODIFY_SCOPES)?;
         let mut params = json!({});
         if let Some(name) = name {
             json_insert!(params, "name", name);
@@ -672,7 +1086,7 @@ impl Spotify {
         if let Some(description) = description {
             json_insert!(params, "description", description);
         }
-        let url = format!("playlists/{}", playlist_id);
+        let url = format!("playlists/{}", playlist_id.id());
         self.put(&url, None, &params).await
     }
 
@@ -683,8 +1097,9 @@ impl Spotify {
     ///
     /// [Reference](https://developer.spotify.com/web-api/unfollow-playlist/)
     #[maybe_async]
-    pub async fn playlist_unfollow(&self, playlist_id: &str) -> ClientResult<String> {
-        let url = format!("playlists/{}/followers", playlist_id);
+    pub async fn playlist_unfollow(&self, playlist_id: &PlaylistId) -> ClientResult<String> {
+        self.require_any_scope(&PLAYLIST_MODIFY_SCOPES)?;
+        let url = format!("playlists/{}/followers", playlist_id.id());
         self.delete(&url, None, &json!({})).await
     }
 
@@ -692,29 +1107,35 @@ impl Spotify {
     ///
     /// Parameters:
     /// - playlist_id - the id of the playlist
-    /// - track_ids - a list of track URIs, URLs or IDs
+    /// - track_ids - a list of track IDs
     /// - position - the position to add the tracks
     ///
     /// [Reference](https://developer.spotify.com/web-api/add-tracks-to-playlist/)
     #[maybe_async]
     pub async fn playlist_add_tracks<'a>(
         &self,
-        playlist_id: &str,
-        track_ids: impl IntoIterator<Item = &'a str>,
+        playlist_id: &PlaylistId,
+        track_ids: impl IntoIterator<Item = &'a TrackId>,
         position: Option<i32>,
     ) -> ClientResult<PlaylistResult> {
-        let plid = self.get_id(Type::Playlist, playlist_id);
-        let uris: Vec<String> = track_ids
-            .into_iter()
-            .map(|id| self.get_uri(Type::Track, id))
-            .collect();
-        let mut params = json!({ "uris": uris });
-        if let Some(position) = position {
-            json_insert!(params, "position", position);
+        self.require_any_scope(&PLAYLIST_MODIFY_SCOPES)?;
+        let uris: Vec<String> = track_ids.into_iter().map(|id| id.uri()).collect();
+        let url = format!("playlists/{}/tracks", playlist_id.id());
+
+        // The chunks are added in order, so the position advances after each
+        // of them. The last snapshot is returned.
+        let mut position = position;
+        let mut snapshot = None;
+        for chunk in chunks(&uris, 100) {
+            let mut params = json!({ "uris": chunk });
+            if let Some(position) = position {
+                json_insert!(params, "position", position);
+            }
+            let result = self.post(&url, None, &params).await?;
+            snapshot = Some(self.convert_result(&result)?);
+            position = position.map(|position| position + chunk.len() as i32);
         }
-        let url = format!("playlists/{}/tracks", plid);
-        let result = self.post(&url, None, &params).await?;
-        self.convert_result(&result)
+        Ok(snapshot.unwrap())
     }
 
     /// Replace all tracks in a playlist
@@ -728,19 +1149,22 @@ impl Spotify {
     #[maybe_async]
     pub async fn playlist_replace_tracks<'a>(
         &self,
-        playlist_id: &str,
-        track_ids: impl IntoIterator<Item = &'a str>,
+        playlist_id: &PlaylistId,
+        track_ids: impl IntoIterator<Item = &'a TrackId>,
     ) -> ClientResult<()> {
-        let plid = self.get_id(Type::Playlist, playlist_id);
-        let uris: Vec<String> = track_ids
-            .into_iter()
-            .map(|id| self.get_uri(Type::Track, id))
-            .collect();
-        // let mut params = Map::new();
-        // params.insert("uris".to_owned(), uris.into());
-        let params = json!({ "uris": uris });
-        let url = format!("playlists/{}/tracks", plid);
-        self.put(&url, None, &params).await?;
+        self.require_any_scope(&PLAYLIST_MODIFY_SCOPES)?;
+        let uris: Vec<String> = track_ids.into_iter().map(|id| id.uri()).collect();
+        let url = format!("playlists/{}/tracks", playlist_id.id());
+
+        // Only up to 100 tracks can be replaced at once, so the first chunk
+        // replaces the tracks and the rest are appended to them in order.
+        let mut chunks = chunks(&uris, 100).into_iter();
+        if let Some(chunk) = chunks.next() {
+            self.put(&url, None, &json!({ "uris": chunk })).await?;
+        }
+        for chunk in chunks {
+            self.post(&url, None, &json!({ "uris": chunk })).await?;
+        }
 
         Ok(())
     }
@@ -758,13 +1182,13 @@ impl Spotify {
     #[maybe_async]
     pub async fn playlist_reorder_tracks<R: Into<Option<u32>>>(
         &self,
-        playlist_id: &str,
+        playlist_id: &PlaylistId,
         range_start: i32,
         range_length: R,
         insert_before: i32,
         snapshot_id: Option<String>,
     ) -> ClientResult<PlaylistResult> {
-        let plid = self.get_id(Type::Playlist, playlist_id);
+        self.require_any_scope(&PLAYLIST_MODIFY_SCOPES)?;
         let mut params = json! ({
             "range_start": range_start,
             "range_length": range_length.into().unwrap_or(1),
@@ -774,7 +1198,7 @@ impl Spotify {
             json_insert!(params, "snapshot_id", snapshot_id);
         }
 
-        let url = format!("playlists/{}/tracks", plid);
+        let url = format!("playlists/{}/tracks", playlist_id.id());
         let result = self.put(&url, None, &params).await?;
         self.convert_result(&result)
     }
@@ -790,88 +1214,65 @@ impl Spotify {
     #[maybe_async]
     pub async fn playlist_remove_all_occurrences_of_tracks<'a>(
         &self,
-        playlist_id: &str,
-        track_ids: impl IntoIterator<Item = &'a str>,
+        playlist_id: &PlaylistId,
+        track_ids: impl IntoIterator<Item = &'a TrackId>,
         snapshot_id: Option<String>,
     ) -> ClientResult<PlaylistResult> {
-        let plid = self.get_id(Type::Playlist, playlist_id);
-        let uris: Vec<String> = track_ids
+        self.require_any_scope(&PLAYLIST_MODIFY_SCOPES)?;
+        let tracks: Vec<Value> = track_ids
             .into_iter()
-            .map(|id| self.get_uri(Type::Track, id))
+            .map(|id| json!({ "uri": id.uri() }))
             .collect();
-
-        // TODO: this can be improved
-        let mut tracks: Vec<Map<String, Value>> = vec![];
-        for uri in uris {
-            let mut map = Map::new();
-            map.insert("uri".to_owned(), uri.into());
-            tracks.push(map);
-        }
-        let mut params = json!({ "tracks": tracks });
-        if let Some(snapshot_id) = snapshot_id {
-            json_insert!(params, "snapshot_id", snapshot_id);
+        let url = format!("playlists/{}/tracks", playlist_id.id());
+
+        // Each chunk is removed from the snapshot left by the previous one.
+        let mut snapshot_id = snapshot_id;
+        let mut snapshot = None;
+        for chunk in chunks(&tracks, 100) {
+            let mut params = json!({ "tracks": chunk });
+            if let Some(snapshot_id) = snapshot_id {
+                json_insert!(params, "snapshot_id", snapshot_id);
+            }
+            let result = self.delete(&url, None, &params).await?;
+            let result: PlaylistResult = self.convert_result(&result)?;
+            snapshot_id = Some(result.snapshot_id.clone());
+            snapshot = Some(result);
         }
-        let url = format!("playlists/{}/tracks", plid);
-        let result = self.delete(&url, None, &params).await?;
-        self.convert_result(&result)
+        Ok(snapshot.unwrap())
     }
 
     /// Removes specfic occurrences of the given tracks from the given playlist.
     ///
     /// Parameters:
     /// - playlist_id: the id of the playlist
-    /// - tracks: an array of map containing Spotify URIs of the tracks to remove
-    /// with their current positions in the playlist. For example:
-    ///
-    /// ```json
-    /// {
-    ///    "tracks":[
-    ///       {
-    ///          "uri":"spotify:track:4iV5W9uYEdYUVa79Axb7Rh",
-    ///          "positions":[
-    ///             0,
-    ///             3
-    ///          ]
-    ///       },
-    ///       {
-    ///          "uri":"spotify:track:1301WleyT98MSxVHPZCA6M",
-    ///          "positions":[
-    ///             7
-    ///          ]
-    ///       }
-    ///    ]
-    /// }
-    /// ```
+    /// - tracks: a list of the tracks to remove with their current positions
+    ///   in the playlist.
     /// - snapshot_id: optional id of the playlist snapshot
     ///
     /// [Reference](https://developer.spotify.com/web-api/remove-tracks-playlist/)
     #[maybe_async]
-    pub async fn playlist_remove_specific_occurrences_of_tracks(
+    pub async fn playlist_remove_specific_occurrences_of_tracks<'a>(
         &self,
-        playlist_id: &str,
-        tracks: Vec<Map<String, Value>>,
+        playlist_id: &PlaylistId,
+        tracks: impl IntoIterator<Item = &'a TrackPositions>,
         snapshot_id: Option<String>,
     ) -> ClientResult<PlaylistResult> {
-        // TODO: this can be improved
-        let plid = self.get_id(Type::Playlist, playlist_id);
-        let mut ftracks: Vec<Map<String, Value>> = vec![];
-        for track in tracks {
-            let mut map = Map::new();
-            if let Some(_uri) = track.get("uri") {
-                let uri = self.get_uri(Type::Track, &_uri.as_str().unwrap().to_owned());
-                map.insert("uri".to_owned(), uri.into());
-            }
-            if let Some(_position) = track.get("position") {
-                map.insert("position".to_owned(), _position.to_owned());
-            }
-            ftracks.push(map);
-        }
+        self.require_any_scope(&PLAYLIST_MODIFY_SCOPES)?;
+        let tracks: Vec<Value> = tracks
+            .into_iter()
+            .map(|track| {
+                json!({
+                    "uri": track.id.uri(),
+                    "positions": track.positions
+                })
+            })
+            .collect();
 
-        let mut params = json!({ "tracks": ftracks });
+        let mut params = json!({ "tracks": tracks });
         if let Some(snapshot_id) = snapshot_id {
             json_insert!(params, "snapshot_id", snapshot_id);
         }
-        let url = format!("playlists/{}/tracks", plid);
+        let url = format!("playlists/{}/tracks", playlist_id.id());
         let result = self.delete(&url, None, &params).await?;
         self.convert_result(&result)
     }
@@ -885,16 +1286,18 @@ impl Spotify {
     #[maybe_async]
     pub async fn playlist_follow<P: Into<Option<bool>>>(
         &self,
-        playlist_id: &str,
+        playlist_id: &PlaylistId,
         public: P,
     ) -> ClientResult<()> {
-        let url = format!("playlists/{}/followers", playlist_id);
+        let public = public.into().unwrap_or(true);
+        self.require_scopes(&[playlist_modify_scope(public)])?;
+        let url = format!("playlists/{}/followers", playlist_id.id());
 
         self.put(
             &url,
             None,
             &json! ({
-                "public": public.into().unwrap_or(true)
+                "public": public
             }),
         )
         .await?;
@@ -913,21 +1316,64 @@ impl Spotify {
     #[maybe_async]
     pub async fn playlist_check_follow(
         &self,
-        playlist_id: &str,
-        user_ids: &[String],
+        playlist_id: &PlaylistId,
+        user_ids: &[UserId],
     ) -> ClientResult<Vec<bool>> {
         if user_ids.len() > 5 {
             error!("The maximum length of user ids is limited to 5 :-)");
         }
         let url = format!(
             "playlists/{}/followers/contains?ids={}",
-            playlist_id,
-            user_ids.join(",")
+            playlist_id.id(),
+            join_ids(user_ids)
         );
         let result = self.get(&url, None, &Query::new()).await?;
         self.convert_result(&result)
     }
 
+    /// Get the current image associated with a playlist. There may be none,
+    /// or up to three of different sizes when it's generated from the
+    /// tracks.
+    ///
+    /// Parameters:
+    /// - playlist_id - the id of the playlist
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/playlists/get-playlist-cover/)
+    #[maybe_async]
+    pub async fn playlist_cover_image(&self, playlist_id: &PlaylistId) -> ClientResult<Vec<Image>> {
+        let url = format!("playlists/{}/images", playlist_id.id());
+        let result = self.get(&url, None, &Query::new()).await?;
+        self.convert_result(&result)
+    }
+
+    /// Replace the image used to represent a playlist. The image is sent
+    /// encoded in base64, which can't be larger than `MAX_COVER_IMAGE_SIZE`,
+    /// or `ClientError::ImageTooLarge` is returned before the request.
+    ///
+    /// Parameters:
+    /// - playlist_id - the id of the playlist
+    /// - image - the contents of the JPEG image
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/playlists/upload-custom-playlist-cover/)
+    #[maybe_async]
+    pub async fn playlist_upload_cover_image(
+        &self,
+        playlist_id: &PlaylistId,
+        image: &[u8],
+    ) -> ClientResult<()> {
+        self.require_scopes(&[Scope::UgcImageUpload])?;
+        self.require_any_scope(&PLAYLIST_MODIFY_SCOPES)?;
+        let data = base64::encode(image);
+        if data.len() > MAX_COVER_IMAGE_SIZE {
+            return Err(ClientError::ImageTooLarge(data.len()));
+        }
+
+        let url = format!("playlists/{}/images", playlist_id.id());
+        self.put_raw(&url, None, "image/jpeg", &data).await?;
+
+        Ok(())
+    }
+
     /// Get detailed profile information about the current user.
     /// An alias for the 'current_user' method.
     ///
@@ -954,6 +1400,10 @@ impl Spotify {
     pub async fn current_user_playing_track(
         &self,
     ) -> ClientResult<Option<CurrentlyPlayingContext>> {
+        self.require_any_scope(&[
+            Scope::UserReadCurrentlyPlaying,
+            Scope::UserReadPlaybackState,
+        ])?;
         let result = self
             .get("me/player/currently-playing", None, &Query::new())
             .await?;
@@ -979,6 +1429,7 @@ impl Spotify {
         limit: L,
         offset: O,
     ) -> ClientResult<Page<SavedAlbum>> {
+        self.require_scopes(&[Scope::UserLibraryRead])?;
         let mut params = Query::with_capacity(2);
         params.insert("limit".to_owned(), limit.into().unwrap_or(20).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
@@ -986,6 +1437,20 @@ impl Spotify {
         self.convert_result(&result)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::current_user_saved_albums`
+        /// ](#method.current_user_saved_albums), which returns all of the
+        /// albums saved by the current user.
+        pub fn current_user_saved_albums_stream | current_user_saved_albums_iter<'a>(
+            &self,
+        ) -> SavedAlbum {
+            paginate(
+                move |limit, offset| self.current_user_saved_albums(limit, offset),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Get a list of the songs saved in the current Spotify user's "Your Music"
     /// library.
     ///
@@ -1001,6 +1466,7 @@ impl Spotify {
         limit: L,
         offset: O,
     ) -> ClientResult<Page<SavedTrack>> {
+        self.require_scopes(&[Scope::UserLibraryRead])?;
         let mut params = Query::with_capacity(2);
         params.insert("limit".to_owned(), limit.into().unwrap_or(20).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
@@ -1008,6 +1474,20 @@ impl Spotify {
         self.convert_result(&result)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::current_user_saved_tracks`
+        /// ](#method.current_user_saved_tracks), which returns all of the
+        /// tracks saved by the current user.
+        pub fn current_user_saved_tracks_stream | current_user_saved_tracks_iter<'a>(
+            &self,
+        ) -> SavedTrack {
+            paginate(
+                move |limit, offset| self.current_user_saved_tracks(limit, offset),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Gets a list of the artists followed by the current authorized user.
     ///
     /// Parameters:
@@ -1022,6 +1502,7 @@ impl Spotify {
         limit: L,
         after: Option<String>,
     ) -> ClientResult<CursorBasedPage<FullArtist>> {
+        self.require_scopes(&[Scope::UserFollowRead])?;
         let mut params = Query::with_capacity(2);
         params.insert("limit".to_owned(), limit.into().unwrap_or(20).to_string());
         params.insert("type".to_owned(), Type::Artist.to_string());
@@ -1034,23 +1515,36 @@ impl Spotify {
             .map(|x| x.artists)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::current_user_followed_artists`
+        /// ](#method.current_user_followed_artists), which returns all of the
+        /// artists followed by the current user.
+        pub fn current_user_followed_artists_stream | current_user_followed_artists_iter<'a>(
+            &self,
+        ) -> FullArtist {
+            paginate_cursor(
+                move |limit, after| self.current_user_followed_artists(limit, after),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Remove one or more tracks from the current user's "Your Music" library.
     ///
     /// Parameters:
-    /// - track_ids - a list of track URIs, URLs or IDs
+    /// - track_ids - a list of track IDs
     ///
     /// [Reference](https://developer.spotify.com/web-api/remove-tracks-user/)
     #[maybe_async]
     pub async fn current_user_saved_tracks_delete<'a>(
         &self,
-        track_ids: impl IntoIterator<Item = &'a str>,
+        track_ids: impl IntoIterator<Item = &'a TrackId>,
     ) -> ClientResult<()> {
-        let uris: Vec<String> = track_ids
-            .into_iter()
-            .map(|id| self.get_id(Type::Track, id))
-            .collect();
-        let url = format!("me/tracks/?ids={}", uris.join(","));
-        self.delete(&url, None, &json!({})).await?;
+        self.require_scopes(&[Scope::UserLibraryModify])?;
+        for ids in chunk_ids(track_ids, 50) {
+            let url = format!("me/tracks/?ids={}", ids);
+            self.delete(&url, None, &json!({})).await?;
+        }
 
         Ok(())
     }
@@ -1059,40 +1553,41 @@ impl Spotify {
     /// user’s "Your Music" library.
     ///
     /// Parameters:
-    /// - track_ids - a list of track URIs, URLs or IDs
+    /// - track_ids - a list of track IDs
     ///
     /// [Reference](https://developer.spotify.com/web-api/check-users-saved-tracks/)
     #[maybe_async]
     pub async fn current_user_saved_tracks_contains<'a>(
         &self,
-        track_ids: impl IntoIterator<Item = &'a str>,
+        track_ids: impl IntoIterator<Item = &'a TrackId>,
     ) -> ClientResult<Vec<bool>> {
-        let uris: Vec<String> = track_ids
-            .into_iter()
-            .map(|id| self.get_id(Type::Track, id))
-            .collect();
-        let url = format!("me/tracks/contains/?ids={}", uris.join(","));
-        let result = self.get(&url, None, &Query::new()).await?;
-        self.convert_result(&result)
+        self.require_scopes(&[Scope::UserLibraryRead])?;
+        let results = self
+            .get_chunks(
+                "me/tracks/contains/",
+                chunk_ids(track_ids, 50),
+                &Query::new(),
+            )
+            .await?;
+        self.concat_results(&results)
     }
 
     /// Save one or more tracks to the current user's "Your Music" library.
     ///
     /// Parameters:
-    /// - track_ids - a list of track URIs, URLs or IDs
+    /// - track_ids - a list of track IDs
     ///
     /// [Reference](https://developer.spotify.com/web-api/save-tracks-user/)
     #[maybe_async]
     pub async fn current_user_saved_tracks_add<'a>(
         &self,
-        track_ids: impl IntoIterator<Item = &'a str>,
+        track_ids: impl IntoIterator<Item = &'a TrackId>,
     ) -> ClientResult<()> {
-        let uris: Vec<String> = track_ids
-            .into_iter()
-            .map(|id| self.get_id(Type::Track, id))
-            .collect();
-        let url = format!("me/tracks/?ids={}", uris.join(","));
-        self.put(&url, None, &json!({})).await?;
+        self.require_scopes(&[Scope::UserLibraryModify])?;
+        for ids in chunk_ids(track_ids, 50) {
+            let url = format!("me/tracks/?ids={}", ids);
+            self.put(&url, None, &json!({})).await?;
+        }
 
         Ok(())
     }
@@ -1116,6 +1611,7 @@ impl Spotify {
         offset: O,
         time_range: T,
     ) -> ClientResult<Page<FullArtist>> {
+        self.require_scopes(&[Scope::UserTopRead])?;
         let mut params = Query::with_capacity(3);
         params.insert("limit".to_owned(), limit.into().unwrap_or(20).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
@@ -1130,6 +1626,21 @@ impl Spotify {
         self.convert_result(&result)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::current_user_top_artists`
+        /// ](#method.current_user_top_artists), which returns all of the
+        /// current user's top artists.
+        pub fn current_user_top_artists_stream | current_user_top_artists_iter<'a>(
+            &self,
+            time_range: Option<TimeRange>,
+        ) -> FullArtist {
+            paginate(
+                move |limit, offset| self.current_user_top_artists(limit, offset, time_range),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Get the current user's top tracks.
     ///
     /// Parameters:
@@ -1149,6 +1660,7 @@ impl Spotify {
         offset: O,
         time_range: T,
     ) -> ClientResult<Page<FullTrack>> {
+        self.require_scopes(&[Scope::UserTopRead])?;
         let mut params = Query::with_capacity(3);
         params.insert("limit".to_owned(), limit.into().unwrap_or(20).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
@@ -1163,40 +1675,89 @@ impl Spotify {
         self.convert_result(&result)
     }
 
-    /// Get the current user's recently played tracks.
+    paginated! {
+        /// The paginated version of [`Spotify::current_user_top_tracks`
+        /// ](#method.current_user_top_tracks), which returns all of the current
+        /// user's top tracks.
+        pub fn current_user_top_tracks_stream | current_user_top_tracks_iter<'a>(
+            &self,
+            time_range: Option<TimeRange>,
+        ) -> FullTrack {
+            paginate(
+                move |limit, offset| self.current_user_top_tracks(limit, offset, time_range),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
+    /// Get the current user's recently played tracks, from the most recent
+    /// one.
     ///
     /// Parameters:
     /// - limit - the number of entities to return
+    /// - before - the cursor to the page of older items, from the `before`
+    ///   cursor of the previous page
     ///
     /// [Reference](https://developer.spotify.com/web-api/web-api-personalization-endpoints/get-recently-played/)
     #[maybe_async]
     pub async fn current_user_recently_played<L: Into<Option<u32>>>(
         &self,
         limit: L,
+        before: Option<String>,
     ) -> ClientResult<CursorBasedPage<PlayHistory>> {
-        let mut params = Query::with_capacity(1);
+        self.require_scopes(&[Scope::UserReadRecentlyPlayed])?;
+        let mut params = Query::with_capacity(2);
         params.insert("limit".to_owned(), limit.into().unwrap_or(50).to_string());
+        if let Some(before) = before {
+            params.insert("before".to_owned(), before);
+        }
         let result = self.get("me/player/recently-played", None, &params).await?;
         self.convert_result(&result)
     }
 
+    /// The recently played tracks are paginated backwards, so the `before`
+    /// cursor is the one followed by `paginate_cursor`.
+    #[maybe_async]
+    async fn current_user_recently_played_page(
+        &self,
+        limit: u32,
+        before: Option<String>,
+    ) -> ClientResult<CursorBasedPage<PlayHistory>> {
+        let mut page = self.current_user_recently_played(limit, before).await?;
+        page.cursors.after = page.cursors.before.take();
+        Ok(page)
+    }
+
+    paginated! {
+        /// The paginated version of [`Spotify::current_user_recently_played`
+        /// ](#method.current_user_recently_played), which returns all of the
+        /// recently played tracks available, from the most recent one.
+        pub fn current_user_recently_played_stream | current_user_recently_played_iter<'a>(
+            &self,
+        ) -> PlayHistory {
+            paginate_cursor(
+                move |limit, before| self.current_user_recently_played_page(limit, before),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Add one or more albums to the current user's "Your Music" library.
     ///
     /// Parameters:
-    /// - album_ids - a list of album URIs, URLs or IDs
+    /// - album_ids - a list of album IDs
     ///
     /// [Reference](https://developer.spotify.com/web-api/save-albums-user/)
     #[maybe_async]
     pub async fn current_user_saved_albums_add<'a>(
         &self,
-        album_ids: impl IntoIterator<Item = &'a str>,
+        album_ids: impl IntoIterator<Item = &'a AlbumId>,
     ) -> ClientResult<()> {
-        let uris: Vec<String> = album_ids
-            .into_iter()
-            .map(|id| self.get_id(Type::Album, id))
-            .collect();
-        let url = format!("me/albums/?ids={}", uris.join(","));
-        self.put(&url, None, &json!({})).await?;
+        self.require_scopes(&[Scope::UserLibraryModify])?;
+        for ids in chunk_ids(album_ids, 50) {
+            let url = format!("me/albums/?ids={}", ids);
+            self.put(&url, None, &json!({})).await?;
+        }
 
         Ok(())
     }
@@ -1204,20 +1765,19 @@ impl Spotify {
     /// Remove one or more albums from the current user's "Your Music" library.
     ///
     /// Parameters:
-    /// - album_ids - a list of album URIs, URLs or IDs
+    /// - album_ids - a list of album IDs
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/library/remove-albums-user/)
     #[maybe_async]
     pub async fn current_user_saved_albums_delete<'a>(
         &self,
-        album_ids: impl IntoIterator<Item = &'a str>,
+        album_ids: impl IntoIterator<Item = &'a AlbumId>,
     ) -> ClientResult<()> {
-        let uris: Vec<String> = album_ids
-            .into_iter()
-            .map(|id| self.get_id(Type::Album, id))
-            .collect();
-        let url = format!("me/albums/?ids={}", uris.join(","));
-        self.delete(&url, None, &json!({})).await?;
+        self.require_scopes(&[Scope::UserLibraryModify])?;
+        for ids in chunk_ids(album_ids, 50) {
+            let url = format!("me/albums/?ids={}", ids);
+            self.delete(&url, None, &json!({})).await?;
+        }
 
         Ok(())
     }
@@ -1226,21 +1786,23 @@ impl Spotify {
     /// user’s "Your Music” library.
     ///
     /// Parameters:
-    /// - album_ids - a list of album URIs, URLs or IDs
+    /// - album_ids - a list of album IDs
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/library/check-users-saved-albums/)
     #[maybe_async]
     pub async fn current_user_saved_albums_contains<'a>(
         &self,
-        album_ids: impl IntoIterator<Item = &'a str>,
+        album_ids: impl IntoIterator<Item = &'a AlbumId>,
     ) -> ClientResult<Vec<bool>> {
-        let uris: Vec<String> = album_ids
-            .into_iter()
-            .map(|id| self.get_id(Type::Album, id))
-            .collect();
-        let url = format!("me/albums/contains/?ids={}", uris.join(","));
-        let result = self.get(&url, None, &Query::new()).await?;
-        self.convert_result(&result)
+        self.require_scopes(&[Scope::UserLibraryRead])?;
+        let results = self
+            .get_chunks(
+                "me/albums/contains/",
+                chunk_ids(album_ids, 20),
+                &Query::new(),
+            )
+            .await?;
+        self.concat_results(&results)
     }
 
     /// Follow one or more artists.
@@ -1252,13 +1814,13 @@ impl Spotify {
     #[maybe_async]
     pub async fn user_follow_artists<'a>(
         &self,
-        artist_ids: impl IntoIterator<Item = &'a str>,
+        artist_ids: impl IntoIterator<Item = &'a ArtistId>,
     ) -> ClientResult<()> {
-        let url = format!(
-            "me/following?type=artist&ids={}",
-            artist_ids.into_iter().collect::<Vec<_>>().join(",")
-        );
-        self.put(&url, None, &json!({})).await?;
+        self.require_scopes(&[Scope::UserFollowModify])?;
+        for ids in chunk_ids(artist_ids, 50) {
+            let url = format!("me/following?type=artist&ids={}", ids);
+            self.put(&url, None, &json!({})).await?;
+        }
 
         Ok(())
     }
@@ -1272,13 +1834,13 @@ impl Spotify {
     #[maybe_async]
     pub async fn user_unfollow_artists<'a>(
         &self,
-        artist_ids: impl IntoIterator<Item = &'a str>,
+        artist_ids: impl IntoIterator<Item = &'a ArtistId>,
     ) -> ClientResult<()> {
-        let url = format!(
-            "me/following?type=artist&ids={}",
-            artist_ids.into_iter().collect::<Vec<_>>().join(",")
-        );
-        self.delete(&url, None, &json!({})).await?;
+        self.require_scopes(&[Scope::UserFollowModify])?;
+        for ids in chunk_ids(artist_ids, 50) {
+            let url = format!("me/following?type=artist&ids={}", ids);
+            self.delete(&url, None, &json!({})).await?;
+        }
 
         Ok(())
     }
@@ -1293,32 +1855,33 @@ impl Spotify {
     #[maybe_async]
     pub async fn user_artist_check_follow<'a>(
         &self,
-        artsit_ids: impl IntoIterator<Item = &'a str>,
+        artist_ids: impl IntoIterator<Item = &'a ArtistId>,
     ) -> ClientResult<Vec<bool>> {
-        let url = format!(
-            "me/following/contains?type=artist&ids={}",
-            artsit_ids.into_iter().collect::<Vec<_>>().join(",")
-        );
-        let result = self.get(&url, None, &Query::new()).await?;
-        self.convert_result(&result)
+        self.require_scopes(&[Scope::UserFollowRead])?;
+        let mut params = Query::new();
+        params.insert("type".to_owned(), "artist".to_owned());
+        let results = self
+            .get_chunks("me/following/contains", chunk_ids(artist_ids, 50), &params)
+            .await?;
+        self.concat_results(&results)
     }
 
     /// Follow one or more users.
     ///
     /// Parameters:
-    /// - user_ids - a list of artist IDs
+    /// - user_ids - a list of user IDs
     ///
     /// [Reference](https://developer.spotify.com/web-api/follow-artists-users/)
     #[maybe_async]
     pub async fn user_follow_users<'a>(
         &self,
-        user_ids: impl IntoIterator<Item = &'a str>,
+        user_ids: impl IntoIterator<Item = &'a UserId>,
     ) -> ClientResult<()> {
-        let url = format!(
-            "me/following?type=user&ids={}",
-            user_ids.into_iter().collect::<Vec<_>>().join(",")
-        );
-        self.put(&url, None, &json!({})).await?;
+        self.require_scopes(&[Scope::UserFollowModify])?;
+        for ids in chunk_ids(user_ids, 50) {
+            let url = format!("me/following?type=user&ids={}", ids);
+            self.put(&url, None, &json!({})).await?;
+        }
 
         Ok(())
     }
@@ -1326,19 +1889,19 @@ impl Spotify {
     /// Unfollow one or more users.
     ///
     /// Parameters:
-    /// - user_ids - a list of artist IDs
+    /// - user_ids - a list of user IDs
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/follow/unfollow-artists-users/)
     #[maybe_async]
     pub async fn user_unfollow_users<'a>(
         &self,
-        user_ids: impl IntoIterator<Item = &'a str>,
+        user_ids: impl IntoIterator<Item = &'a UserId>,
     ) -> ClientResult<()> {
-        let url = format!(
-            "me/following?type=user&ids={}",
-            user_ids.into_iter().collect::<Vec<_>>().join(",")
-        );
-        self.delete(&url, None, &json!({})).await?;
+        self.require_scopes(&[Scope::UserFollowModify])?;
+        for ids in chunk_ids(user_ids, 50) {
+            let url = format!("me/following?type=user&ids={}", ids);
+            self.delete(&url, None, &json!({})).await?;
+        }
 
         Ok(())
     }
@@ -1346,9 +1909,8 @@ impl Spotify {
     /// Get a list of Spotify featured playlists.
     ///
     /// Parameters:
-    /// - locale - The desired language, consisting of a lowercase ISO 639
-    ///   language code and an uppercase ISO 3166-1 alpha-2 country code,
-    ///   joined by an underscore.
+    /// - locale - The desired language, consisting of an ISO 639-1 language
+    ///   code and a country.
     /// - country - An ISO 3166-1 alpha-2 country code.
     /// - timestamp - A timestamp in ISO 8601 format: yyyy-MM-ddTHH:mm:ss. Use
     ///   this parameter to specify the user's local time to get results
@@ -1363,7 +1925,7 @@ impl Spotify {
     #[maybe_async]
     pub async fn featured_playlists<L: Into<Option<u32>>, O: Into<Option<u32>>>(
         &self,
-        locale: Option<String>,
+        locale: Option<Locale>,
         country: Option<Country>,
         timestamp: Option<DateTime<Utc>>,
         limit: L,
@@ -1373,7 +1935,7 @@ impl Spotify {
         params.insert("limit".to_owned(), limit.into().unwrap_or(20).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
         if let Some(locale) = locale {
-            params.insert("locale".to_owned(), locale);
+            params.insert("locale".to_owned(), locale.to_string());
         }
         if let Some(country) = country {
             params.insert("country".to_owned(), country.to_string());
@@ -1385,6 +1947,41 @@ impl Spotify {
         self.convert_result(&result)
     }
 
+    /// Only the page of playlists in `featured_playlists`, without its
+    /// message.
+    #[maybe_async]
+    async fn featured_playlists_page(
+        &self,
+        locale: Option<Locale>,
+        country: Option<Country>,
+        timestamp: Option<DateTime<Utc>>,
+        limit: u32,
+        offset: u32,
+    ) -> ClientResult<Page<SimplifiedPlaylist>> {
+        self.featured_playlists(locale, country, timestamp, limit, offset)
+            .await
+            .map(|x| x.playlists)
+    }
+
+    paginated! {
+        /// The paginated version of [`Spotify::featured_playlists`
+        /// ](#method.featured_playlists), which returns all of the featured
+        /// playlists, without the message.
+        pub fn featured_playlists_stream | featured_playlists_iter<'a>(
+            &self,
+            locale: Option<Locale>,
+            country: Option<Country>,
+            timestamp: Option<DateTime<Utc>>,
+        ) -> SimplifiedPlaylist {
+            paginate(
+                move |limit, offset| {
+                    self.featured_playlists_page(locale, country, timestamp, limit, offset)
+                },
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Get a list of new album releases featured in Spotify.
     ///
     /// Parameters:
@@ -1414,12 +2011,27 @@ impl Spotify {
             .map(|x| x.albums)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::new_releases`
+        /// ](#method.new_releases), which returns all of the new album
+        /// releases.
+        pub fn new_releases_stream | new_releases_iter<'a>(
+            &self,
+            country: Option<Country>,
+        ) -> SimplifiedAlbum {
+            paginate(
+                move |limit, offset| self.new_releases(country, limit, offset),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Get a list of new album releases featured in Spotify
     ///
     /// Parameters:
     /// - country - An ISO 3166-1 alpha-2 country code.
-    /// - locale - The desired language, consisting of an ISO 639 language code
-    ///   and an ISO 3166-1 alpha-2 country code, joined by an underscore.
+    /// - locale - The desired language, consisting of an ISO 639-1 language
+    ///   code and a country.
     /// - limit - The maximum number of items to return. Default: 20.
     ///   Minimum: 1. Maximum: 50
     /// - offset - The index of the first item to return. Default: 0 (the first
@@ -1429,7 +2041,7 @@ impl Spotify {
     #[maybe_async]
     pub async fn categories<L: Into<Option<u32>>, O: Into<Option<u32>>>(
         &self,
-        locale: Option<String>,
+        locale: Option<Locale>,
         country: Option<Country>,
         limit: L,
         offset: O,
@@ -1438,7 +2050,7 @@ impl Spotify {
         params.insert("limit".to_owned(), limit.into().unwrap_or(20).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
         if let Some(locale) = locale {
-            params.insert("locale".to_owned(), locale);
+            params.insert("locale".to_owned(), locale.to_string());
         }
         if let Some(country) = country {
             params.insert("country".to_owned(), country.to_string());
@@ -1448,6 +2060,49 @@ impl Spotify {
             .map(|x| x.categories)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::categories`
+        /// ](#method.categories), which returns all of the categories.
+        pub fn categories_stream | categories_iter<'a>(
+            &self,
+            locale: Option<Locale>,
+            country: Option<Country>,
+        ) -> Category {
+            paginate(
+                move |limit, offset| self.categories(locale, country, limit, offset),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
+    /// Get a single category used to tag items in Spotify
+    ///
+    /// Parameters:
+    /// - category_id - The Spotify category ID for the category.
+    /// - locale - The desired language, consisting of an ISO 639-1 language
+    ///   code and a country.
+    /// - country - An ISO 3166-1 alpha-2 country code.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/browse/get-category/)
+    #[maybe_async]
+    pub async fn category(
+        &self,
+        category_id: &str,
+        locale: Option<Locale>,
+        country: Option<Country>,
+    ) -> ClientResult<Category> {
+        let mut params = Query::new();
+        if let Some(locale) = locale {
+            params.insert("locale".to_owned(), locale.to_string());
+        }
+        if let Some(country) = country {
+            params.insert("country".to_owned(), country.to_string());
+        }
+        let url = format!("browse/categories/{}", category_id);
+        let result = self.get(&url, None, &params).await?;
+        self.convert_result(&result)
+    }
+
     /// Get a list of playlists in a category in Spotify
     ///
     /// Parameters:
@@ -1480,12 +2135,31 @@ impl Spotify {
             .map(|x| x.playlists)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::category_playlists`
+        /// ](#method.category_playlists), which returns all of the category's
+        /// playlists.
+        pub fn category_playlists_stream | category_playlists_iter<'a>(
+            &self,
+            category_id: &'a str,
+            country: Option<Country>,
+        ) -> SimplifiedPlaylist {
+            paginate(
+                move |limit, offset| self.category_playlists(category_id, country, limit, offset),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Get Recommendations Based on Seeds
     ///
     /// Parameters:
-    /// - seed_artists - a list of artist IDs, URIs or URLs
-    /// - seed_tracks - a list of artist IDs, URIs or URLs
+    /// - seed_artists - a list of artist IDs
+    /// - seed_tracks - a list of track IDs
     /// - seed_genres - a list of genre names. Available genres for
+    ///   recommendations can be found with `recommendation_genre_seeds`, and
+    ///   they're checked before the request if `validate_genre_seeds` is
+    ///   enabled.
     /// - country - An ISO 3166-1 alpha-2 country code. If provided, all
     ///   results will be playable in this country.
     /// - limit - The maximum number of items to return. Default: 20.
@@ -1498,9 +2172,9 @@ impl Spotify {
     #[maybe_async]
     pub async fn recommendations<L: Into<Option<u32>>>(
         &self,
-        seed_artists: Option<Vec<String>>,
+        seed_artists: Option<&[ArtistId]>,
         seed_genres: Option<Vec<String>>,
-        seed_tracks: Option<Vec<String>>,
+        seed_tracks: Option<&[TrackId]>,
         limit: L,
         country: Option<Country>,
         payload: &Map<String, Value>,
@@ -1537,21 +2211,24 @@ impl Spotify {
         }
 
         if let Some(seed_artists) = seed_artists {
-            let seed_artists_ids = seed_artists
-                .iter()
-                .map(|id| self.get_id(Type::Artist, id))
-                .collect::<Vec<_>>();
-            params.insert("seed_artists".to_owned(), seed_artists_ids.join(","));
+            params.insert("seed_artists".to_owned(), join_ids(seed_artists));
         }
         if let Some(seed_genres) = seed_genres {
+            if self.validate_genre_seeds {
+                let available = self.recommendation_genre_seeds().await?;
+                let unknown: Vec<String> = seed_genres
+                    .iter()
+                    .filter(|genre| !available.contains(genre))
+                    .cloned()
+                    .collect();
+                if !unknown.is_empty() {
+                    return Err(ClientError::UnknownGenreSeeds(unknown));
+                }
+            }
             params.insert("seed_genres".to_owned(), seed_genres.join(","));
         }
         if let Some(seed_tracks) = seed_tracks {
-            let seed_tracks_ids = seed_tracks
-                .iter()
-                .map(|id| self.get_id(Type::Track, id))
-                .collect::<Vec<_>>();
-            params.insert("seed_tracks".to_owned(), seed_tracks_ids.join(","));
+            params.insert("seed_tracks".to_owned(), join_ids(seed_tracks));
         }
         if let Some(country) = country {
             params.insert("market".to_owned(), country.to_string());
@@ -1560,56 +2237,100 @@ impl Spotify {
         self.convert_result(&result)
     }
 
+    /// Get the genres that can be used as seeds in `recommendations`.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/browse/get-recommendation-genres/)
+    #[maybe_async]
+    pub async fn recommendation_genre_seeds(&self) -> ClientResult<Vec<String>> {
+        let result = self
+            .get("recommendations/available-genre-seeds", None, &Query::new())
+            .await?;
+        self.convert_result::<GenreSeeds>(&result).map(|x| x.genres)
+    }
+
+    /// Get the list of markets where Spotify is available. The markets that
+    /// aren't in `Country` yet, like `XK` for Kosovo, are skipped with a
+    /// warning in the logs.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/markets/get-available-markets/)
+    #[maybe_async]
+    pub async fn available_markets(&self) -> ClientResult<Vec<Country>> {
+        let result = self.get("markets", None, &Query::new()).await?;
+        let markets = self.convert_result::<Markets>(&result)?.markets;
+        Ok(markets
+            .iter()
+            .filter_map(|market| match market.parse() {
+                Ok(country) => Some(country),
+                Err(_) => {
+                    log::warn!("Skipping unknown market: {}", market);
+                    None
+                }
+            })
+            .collect())
+    }
+
     /// Get audio features for a track
     ///
     /// Parameters:
-    /// - track - track URI, URL or ID
+    /// - track_id - track ID
     ///
     /// [Reference](https://developer.spotify.com/web-api/get-audio-features/)
     #[maybe_async]
-    pub async fn track_features(&self, track: &str) -> ClientResult<AudioFeatures> {
-        let track_id = self.get_id(Type::Track, track);
-        let url = format!("audio-features/{}", track_id);
+    pub async fn track_features(&self, track_id: &TrackId) -> ClientResult<AudioFeatures> {
+        let url = format!("audio-features/{}", track_id.id());
         let result = self.get(&url, None, &Query::new()).await?;
         self.convert_result(&result)
     }
 
-    /// Get Audio Features for Several Tracks
+    /// Get Audio Features for Several Tracks, requested in chunks of 100.
+    /// Returns `None` if Spotify has no data for any of the chunks, or
+    /// `ClientError::EmptyChunk` if it only lacks some of them, since the
+    /// features wouldn't match the IDs otherwise.
     ///
     /// Parameters:
-    /// - tracks a list of track URIs, URLs or IDs
+    /// - track_ids - a list of track IDs
     ///
     /// [Reference](https://developer.spotify.com/web-api/get-several-audio-features/)
     #[maybe_async]
     pub async fn tracks_features<'a>(
         &self,
-        tracks: impl IntoIterator<Item = &'a str>,
+        track_ids: impl IntoIterator<Item = &'a TrackId>,
     ) -> ClientResult<Option<Vec<AudioFeatures>>> {
-        let ids: Vec<String> = tracks
-            .into_iter()
-            .map(|track| self.get_id(Type::Track, track))
-            .collect();
-        let url = format!("audio-features/?ids={}", ids.join(","));
+        let results = self
+            .get_chunks("audio-features/", chunk_ids(track_ids, 100), &Query::new())
+            .await?;
 
-        let result = self.get(&url, None, &Query::new()).await?;
-        if result.is_empty() {
-            Ok(None)
-        } else {
-            self.convert_result::<Option<AudioFeaturesPayload>>(&result)
-                .map(|option_payload| option_payload.map(|x| x.audio_features))
+        let mut payloads = Vec::with_capacity(results.len());
+        for result in &results {
+            payloads.push(if result.is_empty() {
+                None
+            } else {
+                self.convert_result::<Option<AudioFeaturesPayload>>(result)?
+            });
+        }
+        if payloads.iter().all(Option::is_none) {
+            return Ok(None);
+        }
+
+        let mut features = Vec::new();
+        for payload in payloads {
+            match payload {
+                Some(payload) => features.extend(payload.audio_features),
+                None => return Err(ClientError::EmptyChunk(features.len())),
+            }
         }
+        Ok(Some(features))
     }
 
     /// Get Audio Analysis for a Track
     ///
     /// Parameters:
-    /// - track_id - a track URI, URL or ID
+    /// - track_id - a track ID
     ///
     /// [Reference](https://developer.spotify.com/web-api/get-audio-analysis/)
     #[maybe_async]
-    pub async fn track_analysis(&self, track: &str) -> ClientResult<AudioAnalysis> {
-        let trid = self.get_id(Type::Track, track);
-        let url = format!("audio-analysis/{}", trid);
+    pub async fn track_analysis(&self, track_id: &TrackId) -> ClientResult<AudioAnalysis> {
+        let url = format!("audio-analysis/{}", track_id.id());
         let result = self.get(&url, None, &Query::new()).await?;
         self.convert_result(&result)
     }
@@ -1619,6 +2340,7 @@ impl Spotify {
     /// [Reference](https://developer.spotify.com/web-api/get-a-users-available-devices/)
     #[maybe_async]
     pub async fn device(&self) -> ClientResult<Vec<Device>> {
+        self.require_scopes(&[Scope::UserReadPlaybackState])?;
         let result = self.get("me/player/devices", None, &Query::new()).await?;
         self.convert_result::<DevicePayload>(&result)
             .map(|x| x.devices)
@@ -1639,6 +2361,7 @@ impl Spotify {
         market: Option<Country>,
         additional_types: Option<Vec<AdditionalType>>,
     ) -> ClientResult<Option<CurrentPlaybackContext>> {
+        self.require_scopes(&[Scope::UserReadPlaybackState])?;
         let mut params = Query::new();
         if let Some(market) = market {
             params.insert("country".to_owned(), market.to_string());
@@ -1719,6 +2442,7 @@ impl Spotify {
         device_id: &str,
         force_play: T,
     ) -> ClientResult<()> {
+        self.require_scopes(&[Scope::UserModifyPlaybackState])?;
         self.put(
             "me/player",
             None,
@@ -1734,15 +2458,15 @@ impl Spotify {
 
     /// Start/Resume a User’s Playback.
     ///
-    /// Provide a `context_uri` to start playback or a album, artist, or
-    /// playlist. Provide a `uris` list to start playback of one or more tracks.
-    /// Provide `offset` as {"position": <int>} or {"uri": "<track uri>"} to
-    /// start playback at a particular offset.
+    /// Provide a `context_uri` to start playback of an album, artist,
+    /// playlist or show. Provide a `uris` list to start playback of one or
+    /// more tracks or episodes. Provide `offset` as {"position": <int>} or
+    /// {"uri": "<track uri>"} to start playback at a particular offset.
     ///
     /// Parameters:
     /// - device_id - device target for playback
-    /// - context_uri - spotify context uri to play
-    /// - uris - spotify track uris
+    /// - context_uri - the ID of the context to play
+    /// - uris - the IDs of the tracks or episodes to play
     /// - offset - offset into context by index or track
     /// - position_ms - Indicates from what position to start playback.
     ///
@@ -1751,19 +2475,21 @@ impl Spotify {
     pub async fn start_playback(
         &self,
         device_id: Option<String>,
-        context_uri: Option<String>,
-        uris: Option<Vec<String>>,
+        context_uri: Option<&dyn PlayContextId>,
+        uris: Option<&[&dyn PlayableId]>,
         offset: Option<super::model::Offset>,
         position_ms: Option<u32>,
     ) -> ClientResult<()> {
+        self.require_scopes(&[Scope::UserModifyPlaybackState])?;
         if context_uri.is_some() && uris.is_some() {
             error!("specify either contexxt uri or uris, not both");
         }
         let mut params = json!({});
         if let Some(context_uri) = context_uri {
-            json_insert!(params, "context_uri", context_uri);
+            json_insert!(params, "context_uri", context_uri.uri());
         }
         if let Some(uris) = uris {
+            let uris: Vec<String> = uris.iter().map(|id| id.uri()).collect();
             json_insert!(params, "uris", uris);
         }
         if let Some(offset) = offset {
@@ -1790,6 +2516,7 @@ impl Spotify {
     /// [Reference](https://developer.spotify.com/web-api/pause-a-users-playback/)
     #[maybe_async]
     pub async fn pause_playback(&self, device_id: Option<String>) -> ClientResult<()> {
+        self.require_scopes(&[Scope::UserModifyPlaybackState])?;
         let url = self.append_device_id("me/player/pause", device_id);
         self.put(&url, None, &json!({})).await?;
 
@@ -1804,6 +2531,7 @@ impl Spotify {
     /// [Reference](https://developer.spotify.com/web-api/skip-users-playback-to-next-track/)
     #[maybe_async]
     pub async fn next_track(&self, device_id: Option<String>) -> ClientResult<()> {
+        self.require_scopes(&[Scope::UserModifyPlaybackState])?;
         let url = self.append_device_id("me/player/next", device_id);
         self.post(&url, None, &json!({})).await?;
 
@@ -1818,6 +2546,7 @@ impl Spotify {
     /// [Reference](https://developer.spotify.com/web-api/skip-users-playback-to-previous-track/)
     #[maybe_async]
     pub async fn previous_track(&self, device_id: Option<String>) -> ClientResult<()> {
+        self.require_scopes(&[Scope::UserModifyPlaybackState])?;
         let url = self.append_device_id("me/player/previous", device_id);
         self.post(&url, None, &json!({})).await?;
 
@@ -1837,6 +2566,7 @@ impl Spotify {
         position_ms: u32,
         device_id: Option<String>,
     ) -> ClientResult<()> {
+        self.require_scopes(&[Scope::UserModifyPlaybackState])?;
         let url = self.append_device_id(
             &format!("me/player/seek?position_ms={}", position_ms),
             device_id,
@@ -1855,6 +2585,7 @@ impl Spotify {
     /// [Reference](https://developer.spotify.com/web-api/set-repeat-mode-on-users-playback/)
     #[maybe_async]
     pub async fn repeat(&self, state: RepeatState, device_id: Option<String>) -> ClientResult<()> {
+        self.require_scopes(&[Scope::UserModifyPlaybackState])?;
         let url = self.append_device_id(
             &format!("me/player/repeat?state={}", state.to_string()),
             device_id,
@@ -1873,6 +2604,7 @@ impl Spotify {
     /// [Reference](https://developer.spotify.com/web-api/set-volume-for-users-playback/)
     #[maybe_async]
     pub async fn volume(&self, volume_percent: u8, device_id: Option<String>) -> ClientResult<()> {
+        self.require_scopes(&[Scope::UserModifyPlaybackState])?;
         if volume_percent > 100u8 {
             error!("volume must be between 0 and 100, inclusive");
         }
@@ -1894,16 +2626,28 @@ impl Spotify {
     /// [Reference](https://developer.spotify.com/web-api/toggle-shuffle-for-users-playback/)
     #[maybe_async]
     pub async fn shuffle(&self, state: bool, device_id: Option<String>) -> ClientResult<()> {
+        self.require_scopes(&[Scope::UserModifyPlaybackState])?;
         let url = self.append_device_id(&format!("me/player/shuffle?state={}", state), device_id);
         self.put(&url, None, &json!({})).await?;
 
         Ok(())
     }
 
+    /// Get the item currently playing and the ones in the user's playback
+    /// queue.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/player/get-queue/)
+    #[maybe_async]
+    pub async fn current_user_queue(&self) -> ClientResult<Queue> {
+        self.require_scopes(&[Scope::UserReadPlaybackState])?;
+        let result = self.get("me/player/queue", None, &Query::new()).await?;
+        self.convert_result(&result)
+    }
+
     /// Add an item to the end of the user's playback queue.
     ///
     /// Parameters:
-    /// - uri - THe uri of the item to add, Track or Episode
+    /// - item - The ID of the item to add, Track or Episode
     /// - device id - The id of the device targeting
     /// - If no device ID provided the user's currently active device is targeted
     ///
@@ -1911,10 +2655,11 @@ impl Spotify {
     #[maybe_async]
     pub async fn add_item_to_queue(
         &self,
-        item: String,
+        item: &dyn PlayableId,
         device_id: Option<String>,
     ) -> ClientResult<()> {
-        let url = self.append_device_id(&format!("me/player/queue?uri={}", &item), device_id);
+        self.require_scopes(&[Scope::UserModifyPlaybackState])?;
+        let url = self.append_device_id(&format!("me/player/queue?uri={}", item.uri()), device_id);
         self.post(&url, None, &json!({})).await?;
 
         Ok(())
@@ -1927,10 +2672,15 @@ impl Spotify {
     ///
     /// [Reference](https://developer.spotify.com/console/put-current-user-saved-shows)
     #[maybe_async]
-    pub async fn save_shows<'a>(&self, ids: impl IntoIterator<Item = &'a str>) -> ClientResult<()> {
-        let joined_ids = ids.into_iter().collect::<Vec<_>>().join(",");
-        let url = format!("me/shows/?ids={}", joined_ids);
-        self.put(&url, None, &json!({})).await?;
+    pub async fn save_shows<'a>(
+        &self,
+        ids: impl IntoIterator<Item = &'a ShowId>,
+    ) -> ClientResult<()> {
+        self.require_scopes(&[Scope::UserLibraryModify])?;
+        for ids in chunk_ids(ids, 50) {
+            let url = format!("me/shows/?ids={}", ids);
+            self.put(&url, None, &json!({})).await?;
+        }
 
         Ok(())
     }
@@ -1949,6 +2699,7 @@ impl Spotify {
         limit: L,
         offset: O,
     ) -> ClientResult<Page<Show>> {
+        self.require_scopes(&[Scope::UserLibraryRead])?;
         let mut params = Query::with_capacity(2);
         params.insert("limit".to_owned(), limit.into().unwrap_or(20).to_string());
         params.insert("offset".to_owned(), offset.into().unwrap_or(0).to_string());
@@ -1956,6 +2707,20 @@ impl Spotify {
         self.convert_result(&result)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::get_saved_show`
+        /// ](#method.get_saved_show), which returns all of the shows saved by
+        /// the current user.
+        pub fn get_saved_show_stream | get_saved_show_iter<'a>(
+            &self,
+        ) -> Show {
+            paginate(
+                move |limit, offset| self.get_saved_show(limit, offset),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Get Spotify catalog information for a single show identified by its unique Spotify ID.
     ///
     /// Path Parameters:
@@ -1966,12 +2731,12 @@ impl Spotify {
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/shows/get-a-show/)
     #[maybe_async]
-    pub async fn get_a_show(&self, id: String, market: Option<Country>) -> ClientResult<FullShow> {
+    pub async fn get_a_show(&self, id: &ShowId, market: Option<Country>) -> ClientResult<FullShow> {
         let mut params = Query::new();
         if let Some(market) = market {
             params.insert("country".to_owned(), market.to_string());
         }
-        let url = format!("shows/{}", id);
+        let url = format!("shows/{}", id.id());
         let result = self.get(&url, None, &params).await?;
         self.convert_result(&result)
     }
@@ -1987,21 +2752,24 @@ impl Spotify {
     #[maybe_async]
     pub async fn get_several_shows<'a>(
         &self,
-        ids: impl IntoIterator<Item = &'a str>,
+        ids: impl IntoIterator<Item = &'a ShowId>,
         market: Option<Country>,
     ) -> ClientResult<Vec<SimplifiedShow>> {
-        // TODO: This can probably be better
         let mut params = Query::with_capacity(1);
-        params.insert(
-            "ids".to_owned(),
-            ids.into_iter().collect::<Vec<_>>().join(","),
-        );
         if let Some(market) = market {
             params.insert("country".to_owned(), market.to_string());
         }
-        let result = self.get("shows", None, &params).await?;
-        self.convert_result::<SeversalSimplifiedShows>(&result)
-            .map(|x| x.shows)
+        let results = self
+            .get_chunks("shows", chunk_ids(ids, 50), &params)
+            .await?;
+        let mut shows = Vec::new();
+        for result in results {
+            shows.extend(
+                self.convert_result::<SeversalSimplifiedShows>(&result)?
+                    .shows,
+            );
+        }
+        Ok(shows)
     }
 
     /// Get Spotify catalog information about an show’s episodes. Optional
@@ -2019,7 +2787,7 @@ impl Spotify {
     #[maybe_async]
     pub async fn get_shows_episodes<L: Into<Option<u32>>, O: Into<Option<u32>>>(
         &self,
-        id: String,
+        id: &ShowId,
         limit: L,
         offset: O,
         market: Option<Country>,
@@ -2030,11 +2798,27 @@ impl Spotify {
         if let Some(market) = market {
             params.insert("country".to_owned(), market.to_string());
         }
-        let url = format!("shows/{}/episodes", id);
+        let url = format!("shows/{}/episodes", id.id());
         let result = self.get(&url, None, &params).await?;
         self.convert_result(&result)
     }
 
+    paginated! {
+        /// The paginated version of [`Spotify::get_shows_episodes`
+        /// ](#method.get_shows_episodes), which returns all of the show's
+        /// episodes.
+        pub fn get_shows_episodes_stream | get_shows_episodes_iter<'a>(
+            &self,
+            id: &'a ShowId,
+            market: Option<Country>,
+        ) -> SimplifiedEpisode {
+            paginate(
+                move |limit, offset| self.get_shows_episodes(id, limit, offset, market),
+                DEFAULT_PAGINATION_CHUNKS,
+            )
+        }
+    }
+
     /// Get Spotify catalog information for a single episode identified by its unique Spotify ID.
     ///
     /// Path Parameters
@@ -2047,10 +2831,10 @@ impl Spotify {
     #[maybe_async]
     pub async fn get_an_episode(
         &self,
-        id: String,
+        id: &EpisodeId,
         market: Option<Country>,
     ) -> ClientResult<FullEpisode> {
-        let url = format!("episodes/{}", id);
+        let url = format!("episodes/{}", id.id());
         let mut params = Query::new();
         if let Some(market) = market {
             params.insert("country".to_owned(), market.to_string());
@@ -2070,19 +2854,21 @@ impl Spotify {
     #[maybe_async]
     pub async fn get_several_episodes<'a>(
         &self,
-        ids: impl IntoIterator<Item = &'a str>,
+        ids: impl IntoIterator<Item = &'a EpisodeId>,
         market: Option<Country>,
     ) -> ClientResult<SeveralEpisodes> {
         let mut params = Query::with_capacity(1);
-        params.insert(
-            "ids".to_owned(),
-            ids.into_iter().collect::<Vec<_>>().join(","),
-        );
         if let Some(market) = market {
             params.insert("country".to_owned(), market.to_string());
         }
-        let result = self.get("episodes", None, &params).await?;
-        self.convert_result(&result)
+        let results = self
+            .get_chunks("episodes", chunk_ids(ids, 50), &params)
+            .await?;
+        let mut episodes = Vec::new();
+        for result in results {
+            episodes.extend(self.convert_result::<SeveralEpisodes>(&result)?.episodes);
+        }
+        Ok(SeveralEpisodes { episodes })
     }
 
     /// Check if one or more shows is already saved in the current Spotify user’s library.
@@ -2094,15 +2880,13 @@ impl Spotify {
     #[maybe_async]
     pub async fn check_users_saved_shows<'a>(
         &self,
-        ids: impl IntoIterator<Item = &'a str>,
+        ids: impl IntoIterator<Item = &'a ShowId>,
     ) -> ClientResult<Vec<bool>> {
-        let mut params = Query::with_capacity(1);
-        params.insert(
-            "ids".to_owned(),
-            ids.into_iter().collect::<Vec<_>>().join(","),
-        );
-        let result = self.get("me/shows/contains", None, &params).await?;
-        self.convert_result(&result)
+        self.require_scopes(&[Scope::UserLibraryRead])?;
+        let results = self
+            .get_chunks("me/shows/contains", chunk_ids(ids, 50), &Query::new())
+            .await?;
+        self.concat_results(&results)
     }
 
     /// Delete one or more shows from current Spotify user's library.
@@ -2116,16 +2900,18 @@ impl Spotify {
     #[maybe_async]
     pub async fn remove_users_saved_shows<'a>(
         &self,
-        ids: impl IntoIterator<Item = &'a str>,
+        ids: impl IntoIterator<Item = &'a ShowId>,
         market: Option<Country>,
     ) -> ClientResult<()> {
-        let joined_ids = ids.into_iter().collect::<Vec<_>>().join(",");
-        let url = format!("me/shows?ids={}", joined_ids);
+        self.require_scopes(&[Scope::UserLibraryModify])?;
         let mut params = json!({});
         if let Some(market) = market {
             json_insert!(params, "country", market.to_string());
         }
-        self.delete(&url, None, &params).await?;
+        for ids in chunk_ids(ids, 50) {
+            let url = format!("me/shows?ids={}", ids);
+            self.delete(&url, None, &params).await?;
+        }
 
         Ok(())
     }
@@ -2134,6 +2920,8 @@ impl Spotify {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::oauth2::TokenBuilder;
+    use crate::scopes::{Scope, Scopes};
 
     #[test]
     fn test_parse_response_code() {
@@ -2144,49 +2932,43 @@ mod tests {
     }
 
     #[test]
-    fn test_get_id() {
-        // Assert artist
-        let spotify = SpotifyBuilder::default().build().unwrap();
-        let artist_id = "spotify:artist:2WX2uTcsvV5OnS0inACecP";
-        let id = spotify.get_id(Type::Artist, artist_id);
-        assert_eq!("2WX2uTcsvV5OnS0inACecP", &id);
-
-        // Assert album
-        let artist_id_a = "spotify/album/2WX2uTcsvV5OnS0inACecP";
-        assert_eq!(
-            "2WX2uTcsvV5OnS0inACecP",
-            &spotify.get_id(Type::Album, artist_id_a)
-        );
-
-        // Mismatch type
-        let artist_id_b = "spotify:album:2WX2uTcsvV5OnS0inACecP";
-        assert_eq!(
-            "spotify:album:2WX2uTcsvV5OnS0inACecP",
-            &spotify.get_id(Type::Artist, artist_id_b)
-        );
-
-        // Could not split
-        let artist_id_c = "spotify-album-2WX2uTcsvV5OnS0inACecP";
-        assert_eq!(
-            "spotify-album-2WX2uTcsvV5OnS0inACecP",
-            &spotify.get_id(Type::Artist, artist_id_c)
-        );
+    fn test_token_shared_between_clones() {
+        let tok = TokenBuilder::default()
+            .access_token("test-access-token")
+            .expires_in(3600)
+            .build()
+            .unwrap();
+        let spotify = SpotifyBuilder::default().token(tok).build().unwrap();
+        let cloned = spotify.clone();
+
+        let mut refreshed = spotify.get_token().unwrap();
+        refreshed.access_token = "refreshed-access-token".to_owned();
+        *spotify.token.lock().unwrap() = Some(refreshed);
 
-        let playlist_id = "spotify:playlist:59ZbFPES4DQwEjBpWHzrtC";
         assert_eq!(
-            "59ZbFPES4DQwEjBpWHzrtC",
-            &spotify.get_id(Type::Playlist, playlist_id)
+            cloned.get_token().unwrap().access_token,
+            "refreshed-access-token"
         );
     }
 
     #[test]
-    fn test_get_uri() {
-        let spotify = SpotifyBuilder::default().build().unwrap();
-        let track_id1 = "spotify:track:4iV5W9uYEdYUVa79Axb7Rh";
-        let track_id2 = "1301WleyT98MSxVHPZCA6M";
-        let uri1 = spotify.get_uri(Type::Track, track_id1);
-        let uri2 = spotify.get_uri(Type::Track, track_id2);
-        assert_eq!(track_id1, uri1);
-        assert_eq!("spotify:track:1301WleyT98MSxVHPZCA6M", &uri2);
+    fn test_require_scopes() {
+        let tok = TokenBuilder::default()
+            .access_token("test-access-token")
+            .expires_in(3600)
+            .scope(vec![Scope::UserLibraryRead, Scope::UserTopRead])
+            .build()
+            .unwrap();
+        let spotify = SpotifyBuilder::default().token(tok).build().unwrap();
+
+        assert!(spotify.require_scopes(&[Scope::UserTopRead]).is_ok());
+        let result = spotify.require_scopes(&[Scope::UserTopRead, Scope::UserFollowRead]);
+        assert!(
+            matches!(result, Err(ClientError::MissingScopes(missing)) if missing == Scopes::from(Scope::UserFollowRead))
+        );
+
+        // Tokens without scopes aren't checked
+        spotify.token.lock().unwrap().as_mut().unwrap().scope = Scopes::new();
+        assert!(spotify.require_scopes(&[Scope::UserFollowRead]).is_ok());
     }
 }
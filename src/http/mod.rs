@@ -1,17 +1,29 @@
 //! The HTTP client may vary depending on which one the user configures. This
-//! module contains the required logic to use different clients
-//! interchangeably.
+//! module contains the [`HttpClient`](trait.HttpClient.html) trait, which is
+//! implemented by the clients included in Rspotify and can be implemented for
+//! any other one, and the request logic common to all of them.
 
 #[cfg(feature = "client-reqwest")]
 mod reqwest;
 #[cfg(feature = "client-ureq")]
 mod ureq;
 
-use crate::client::{ClientResult, Spotify};
+#[cfg(feature = "client-reqwest")]
+pub use self::reqwest::ReqwestClient;
+#[cfg(feature = "client-ureq")]
+pub use self::ureq::UreqClient;
+
+use crate::cache::CachedResponse;
+use crate::client::{APIError, ClientError, ClientResult, Spotify};
 
 use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
 
+use derive_builder::Builder;
 use maybe_async::maybe_async;
+use serde::Deserialize;
 use serde_json::Value;
 
 pub type Headers = HashMap<String, String>;
@@ -24,6 +36,12 @@ pub mod headers {
     // Common headers as constants
     pub const CLIENT_ID: &str = "client_id";
     pub const CODE: &str = "code";
+    pub const CODE_CHALLENGE: &str = "code_challenge";
+    pub const CODE_CHALLENGE_METHOD: &str = "code_challenge_method";
+    pub const CODE_CHALLENGE_METHOD_S256: &str = "S256";
+    pub const CODE_VERIFIER: &str = "code_verifier";
+    pub const ETAG: &str = "etag";
+    pub const IF_NONE_MATCH: &str = "if-none-match";
     pub const GRANT_AUTH_CODE: &str = "authorization_code";
     pub const GRANT_CLIENT_CREDS: &str = "client_credentials";
     pub const GRANT_REFRESH_TOKEN: &str = "refresh_token";
@@ -31,7 +49,9 @@ pub mod headers {
     pub const REDIRECT_URI: &str = "redirect_uri";
     pub const REFRESH_TOKEN: &str = "refresh_token";
     pub const RESPONSE_CODE: &str = "code";
+    pub const RESPONSE_TOKEN: &str = "token";
     pub const RESPONSE_TYPE: &str = "response_type";
+    pub const RETRY_AFTER: &str = "retry-after";
     pub const SCOPE: &str = "scope";
     pub const SHOW_DIALOG: &str = "show_dialog";
     pub const STATE: &str = "state";
@@ -54,6 +74,199 @@ pub mod headers {
     }
 }
 
+/// The HTTP methods used by the Spotify API.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+impl Method {
+    /// Whether sending the request more than once has the same effect as
+    /// sending it once, so that it can always be retried.
+    pub fn is_idempotent(self) -> bool {
+        self != Method::Post
+    }
+}
+
+/// The body of a request.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Body {
+    Empty,
+    /// Sent with the `application/json` content type.
+    Json(Value),
+    /// Sent with the `application/x-www-form-urlencoded` content type.
+    Form(Form),
+    /// Sent as it is with the given content type, like the base64 encoded
+    /// images with `image/jpeg`.
+    Raw {
+        content_type: String,
+        data: String,
+    },
+}
+
+/// A request to be sent by the [`HttpClient`](trait.HttpClient.html). The URL
+/// is absolute and the headers already include the authorization.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: Headers,
+    /// The parameters appended to the URL's query.
+    pub query: Query,
+    pub body: Body,
+    /// The maximum duration of the request, which overrides the one the HTTP
+    /// client was configured with. Clients should fail with
+    /// `ClientError::Timeout` when it's exceeded.
+    pub timeout: Option<Duration>,
+}
+
+/// The response to an [`HttpRequest`](struct.HttpRequest.html), whatever its
+/// status is. The names of the headers are lowercase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Headers,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// The value of a header, with its name in any case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    /// Whether the status is `2xx`.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// The status and headers of the response, without its body.
+    pub fn metadata(&self) -> ResponseMetadata {
+        ResponseMetadata {
+            status: self.status,
+            headers: self.headers.clone(),
+        }
+    }
+}
+
+/// The status and headers of a response, recorded by the clients obtained
+/// with [`Spotify::with_response_metadata`
+/// ](../client/struct.Spotify.html#method.with_response_metadata). The names
+/// of the headers are lowercase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResponseMetadata {
+    pub status: u16,
+    pub headers: Headers,
+}
+
+impl ResponseMetadata {
+    /// The value of a header, with its name in any case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    /// The `ETag` header, which can be sent back in `If-None-Match` to make a
+    /// conditional request.
+    pub fn etag(&self) -> Option<&str> {
+        self.header(headers::ETAG)
+    }
+
+    /// The seconds to wait before the next request according to the
+    /// `Retry-After` header, sent when the client is rate limited.
+    pub fn retry_after(&self) -> Option<u64> {
+        self.header(headers::RETRY_AFTER)?.parse().ok()
+    }
+}
+
+/// The HTTP client used to send the requests to Spotify, configured with
+/// [`SpotifyBuilder::http_client`
+/// ](../client/struct.SpotifyBuilder.html#method.http_client). Rspotify
+/// includes [`ReqwestClient`](struct.ReqwestClient.html) for the
+/// `client-reqwest` feature and [`UreqClient`](struct.UreqClient.html) for
+/// `client-ureq`, but it can be implemented for any other client, or for a
+/// test double.
+///
+/// The client only has to send the requests: the authorization, automatic
+/// token refreshing, retries and error handling are the same for all of them.
+/// Its methods are asynchronous with `client-reqwest` and blocking with
+/// `client-ureq`, and the [`async_trait`](https://docs.rs/async-trait) crate is
+/// needed to implement them in the first case.
+#[maybe_async]
+pub trait HttpClient: Debug + Send + Sync {
+    /// Sends the request and returns its response, even if its status isn't
+    /// successful. An error should only be returned when the request couldn't
+    /// be completed, like when the connection failed.
+    async fn send(&self, request: &HttpRequest) -> ClientResult<HttpResponse>;
+
+    /// Waits for the given duration before a request is retried, which
+    /// depends on the runtime.
+    async fn sleep(&self, duration: Duration);
+}
+
+/// The configuration of the HTTP clients included in Rspotify, set with
+/// [`SpotifyBuilder::http_config`
+/// ](../client/struct.SpotifyBuilder.html#method.http_config). It's ignored
+/// when a custom client is used.
+#[derive(Builder, Debug, Default, Clone, PartialEq, Eq)]
+pub struct HttpConfig {
+    /// The maximum duration of a request, from connecting until the whole
+    /// response has been read. No limit by default.
+    #[builder(setter(strip_option), default)]
+    pub timeout: Option<Duration>,
+
+    /// The maximum duration of the connection phase. No limit by default.
+    #[builder(setter(strip_option), default)]
+    pub connect_timeout: Option<Duration>,
+
+    /// The `User-Agent` header sent in the requests, which is the HTTP
+    /// client's own by default.
+    #[builder(setter(into, strip_option), default)]
+    pub user_agent: Option<String>,
+
+    /// The URL of the proxy all the requests go through, like
+    /// `http://localhost:3128` or `socks5://localhost:1080`. None by default.
+    #[builder(setter(into, strip_option), default)]
+    pub proxy: Option<String>,
+}
+
+/// The HTTP client included in Rspotify for the enabled feature.
+pub(crate) fn default_client(config: &HttpConfig) -> ClientResult<Arc<dyn HttpClient>> {
+    #[cfg(feature = "client-reqwest")]
+    let client = ReqwestClient::new(config)?;
+    #[cfg(feature = "client-ureq")]
+    let client = UreqClient::new(config)?;
+
+    Ok(Arc::new(client))
+}
+
+/// The body of the unsuccessful responses, which wraps the error object.
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: APIError,
+}
+
+impl ClientError {
+    /// Converts an unsuccessful response into its error. This is shared by
+    /// all the HTTP clients so that they fail in the same way.
+    pub(crate) fn from_response(response: HttpResponse) -> Self {
+        match response.status {
+            401 => Self::Unauthorized,
+            429 => Self::RateLimited(
+                response
+                    .header(headers::RETRY_AFTER)
+                    .and_then(|duration| duration.parse().ok()),
+            ),
+            status @ 403 | status @ 404 => serde_json::from_str::<ErrorResponse>(&response.body)
+                .map(|response| response.error.into())
+                .unwrap_or(Self::StatusCode(status, response.body)),
+            status => Self::StatusCode(status, response.body),
+        }
+    }
+}
+
 /// The default headers will be overriden if its value is other than None.
 ///
 /// When any of the request doesn't need parameters, the empty or default value
@@ -93,6 +306,15 @@ pub trait BaseClient {
         payload: &Value,
     ) -> ClientResult<String>;
 
+    // For the payloads that aren't JSON, sent with their own content type.
+    async fn put_raw(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+        content_type: &str,
+        payload: &str,
+    ) -> ClientResult<String>;
+
     async fn delete(
         &self,
         url: &str,
@@ -113,4 +335,597 @@ impl Spotify {
             url.to_string()
         }
     }
+
+    /// Returns the authorization headers for a request to the API. If the
+    /// access token has expired, it's refreshed first when possible.
+    #[maybe_async]
+    async fn auth_headers(&self) -> ClientResult<Headers> {
+        if self.get_token()?.is_expired() {
+            self.refresh_token().await?;
+        }
+
+        let mut auth = Headers::new();
+        let (key, val) = headers::bearer_auth(&self.get_token()?);
+        auth.insert(key, val);
+        Ok(auth)
+    }
+
+    /// Sends a request to the Spotify API like the endpoints do, with the
+    /// authorization and retries, but returning its whole response whatever
+    /// its status is. `headers` are added to the authorization ones, so that
+    /// it can be used for conditional requests with `If-None-Match`, which are
+    /// answered with `304 Not Modified` when the resource hasn't changed.
+    ///
+    /// The URL may be relative to the prefix, like `me/player`.
+    #[maybe_async]
+    pub async fn api_response(
+        &self,
+        method: Method,
+        url: &str,
+        headers: &Headers,
+        query: &Query,
+        body: Body,
+    ) -> ClientResult<HttpResponse> {
+        let headers = headers
+            .iter()
+            .map(|(key, val)| (key.to_lowercase(), val.clone()))
+            .collect();
+        self.fetch(method, url, None, &headers, query, body).await
+    }
+
+    /// Sends a request with the HTTP client, returning the body of its
+    /// response if it was successful.
+    #[maybe_async]
+    async fn request(
+        &self,
+        method: Method,
+        url: &str,
+        headers: Option<&Headers>,
+        query: &Query,
+        body: Body,
+    ) -> ClientResult<String> {
+        // Only the GET requests to the API are cached, which are the ones with
+        // the default auth headers.
+        let cache = match self.response_cache {
+            Some(ref cache) if method == Method::Get && headers.is_none() => {
+                Some((cache, self.response_cache_key(url, query)))
+            }
+            _ => None,
+        };
+        let cached = match cache {
+            Some((cache, ref key)) => cache.get(key).await.unwrap_or_else(|err| {
+                log::warn!("Couldn't load the cached response: {}", err);
+                None
+            }),
+            None => None,
+        };
+
+        let mut extra_headers = Headers::new();
+        if let Some(ref cached) = cached {
+            extra_headers.insert(headers::IF_NONE_MATCH.to_owned(), cached.etag.clone());
+        }
+        let response = self
+            .fetch(method, url, headers, &extra_headers, query, body)
+            .await?;
+
+        match cached {
+            Some(cached) if response.status == 304 => return Ok(cached.body),
+            _ if !response.is_success() => return Err(ClientError::from_response(response)),
+            _ => (),
+        }
+
+        if let (Some((cache, key)), Some(etag)) = (cache, response.header(headers::ETAG)) {
+            let cached = CachedResponse {
+                etag: etag.to_owned(),
+                body: response.body.clone(),
+            };
+            if let Err(err) = cache.put(&key, &cached).await {
+                log::warn!("Couldn't cache the response: {}", err);
+            }
+        }
+
+        Ok(response.body)
+    }
+
+    /// The key a response is cached under, which includes the client's cache
+    /// key so that users don't share their responses. The query is sorted so
+    /// that the key is always the same.
+    fn response_cache_key(&self, url: &str, query: &Query) -> String {
+        let mut params: Vec<_> = query
+            .iter()
+            .map(|(key, val)| format!("{}={}", key, val))
+            .collect();
+        params.sort();
+        format!(
+            "{} {}?{}",
+            self.cache_key,
+            self.endpoint_url(url),
+            params.join("&")
+        )
+    }
+
+    /// Sends a request with the HTTP client, retrying it when it fails
+    /// temporarily, and returns its last response. It's recorded if the
+    /// response metadata was requested.
+    #[maybe_async]
+    async fn fetch(
+        &self,
+        method: Method,
+        url: &str,
+        headers: Option<&Headers>,
+        extra_headers: &Headers,
+        query: &Query,
+        body: Body,
+    ) -> ClientResult<HttpResponse> {
+        let mut request = HttpRequest {
+            method,
+            url: self.endpoint_url(url),
+            headers: Headers::new(),
+            query: query.clone(),
+            body,
+            timeout: self.timeout,
+        };
+
+        // Requests that failed temporarily are retried as configured in the
+        // retry policy, waiting between attempts.
+        let mut attempt = 1;
+        let response = loop {
+            let response = self
+                .send_authorized(&mut request, headers, extra_headers)
+                .await?;
+
+            let retry_after = response
+                .header(headers::RETRY_AFTER)
+                .and_then(|duration| duration.parse().ok());
+            let status = response.status;
+            let idempotent = request.method.is_idempotent();
+            match self
+                .retry_policy
+                .retry_delay(attempt, status, idempotent, retry_after)
+            {
+                Some(delay) => {
+                    log::warn!("Request failed with {}, retrying in {:?}", status, delay);
+                    self.http.sleep(delay).await;
+                    attempt += 1;
+                }
+                None => break response,
+            }
+        };
+
+        if let Some(ref last_response) = self.last_response {
+            *last_response.lock().unwrap() = Some(response.metadata());
+        }
+
+        Ok(response)
+    }
+
+    #[maybe_async]
+    async fn send_authorized(
+        &self,
+        request: &mut HttpRequest,
+        headers: Option<&Headers>,
+        extra_headers: &Headers,
+    ) -> ClientResult<HttpResponse> {
+        // The default auth headers are used if none were specified. In that
+        // case, the access token may have been revoked or have expired
+        // earlier than expected, so it's refreshed and the request is retried
+        // once when Spotify rejects it.
+        match headers {
+            Some(headers) => {
+                request.headers = headers.clone();
+                request.headers.extend(extra_headers.clone());
+                self.send(request).await
+            }
+            None => {
+                request.headers = self.auth_headers().await?;
+                request.headers.extend(extra_headers.clone());
+                let response = self.send(request).await?;
+                if response.status == 401 && self.refresh_token().await? {
+                    request.headers = self.auth_headers().await?;
+                    request.headers.extend(extra_headers.clone());
+                    self.send(request).await
+                } else {
+                    Ok(response)
+                }
+            }
+        }
+    }
+
+    #[maybe_async]
+    async fn send(&self, request: &HttpRequest) -> ClientResult<HttpResponse> {
+        // The permit is held until the response is received.
+        let _permit = match self.rate_limiter {
+            Some(ref limiter) => limiter.acquire(self.http.as_ref()).await,
+            None => None,
+        };
+
+        log::info!("Making request {:?} {}", request.method, request.url);
+        self.http.send(request).await
+    }
+}
+
+#[maybe_async]
+impl BaseClient for Spotify {
+    #[inline]
+    async fn get(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+        payload: &Query,
+    ) -> ClientResult<String> {
+        self.request(Method::Get, url, headers, payload, Body::Empty)
+            .await
+    }
+
+    #[inline]
+    async fn post(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+        payload: &Value,
+    ) -> ClientResult<String> {
+        let body = Body::Json(payload.clone());
+        self.request(Method::Post, url, headers, &Query::new(), body)
+            .await
+    }
+
+    #[inline]
+    async fn post_form(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+        payload: &Form,
+    ) -> ClientResult<String> {
+        let body = Body::Form(payload.clone());
+        self.request(Method::Post, url, headers, &Query::new(), body)
+            .await
+    }
+
+    #[inline]
+    async fn put(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+        payload: &Value,
+    ) -> ClientResult<String> {
+        let body = Body::Json(payload.clone());
+        self.request(Method::Put, url, headers, &Query::new(), body)
+            .await
+    }
+
+    #[inline]
+    async fn put_raw(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+        content_type: &str,
+        payload: &str,
+    ) -> ClientResult<String> {
+        let body = Body::Raw {
+            content_type: content_type.to_owned(),
+            data: payload.to_owned(),
+        };
+        self.request(Method::Put, url, headers, &Query::new(), body)
+            .await
+    }
+
+    #[inline]
+    async fn delete(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+        payload: &Value,
+    ) -> ClientResult<String> {
+        let body = Body::Json(payload.clone());
+        self.request(Method::Delete, url, headers, &Query::new(), body)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::MemoryResponseCache;
+    use crate::client::SpotifyBuilder;
+    use crate::model::PlayerErrorReason;
+    use crate::oauth2::TokenBuilder;
+    use crate::ratelimit::{RateLimitBuilder, RateLimiter};
+    use crate::retry::RetryPolicyBuilder;
+
+    use std::sync::Mutex;
+
+    /// Answers the requests with the given responses in order, recording the
+    /// requests and the waits between them.
+    #[derive(Debug, Default)]
+    struct FakeClient {
+        responses: Mutex<Vec<HttpResponse>>,
+        requests: Mutex<Vec<HttpRequest>>,
+        sleeps: Mutex<Vec<Duration>>,
+    }
+
+    #[maybe_async]
+    impl HttpClient for FakeClient {
+        async fn send(&self, request: &HttpRequest) -> ClientResult<HttpResponse> {
+            self.requests.lock().unwrap().push(request.clone());
+            Ok(self.responses.lock().unwrap().remove(0))
+        }
+
+        async fn sleep(&self, duration: Duration) {
+            self.sleeps.lock().unwrap().push(duration);
+        }
+    }
+
+    fn response(status: u16, body: &str) -> HttpResponse {
+        HttpResponse {
+            status,
+            headers: Headers::new(),
+            body: body.to_owned(),
+        }
+    }
+
+    fn client(responses: Vec<HttpResponse>) -> (Spotify, Arc<FakeClient>) {
+        let tok = TokenBuilder::default()
+            .access_token("test-access-token")
+            .expires_in(3600)
+            .expires_at(i64::MAX)
+            .build()
+            .unwrap();
+        let policy = RetryPolicyBuilder::default()
+            .base_delay(Duration::from_secs(1))
+            .jitter(false)
+            .build()
+            .unwrap();
+        let fake = Arc::new(FakeClient {
+            responses: Mutex::new(responses),
+            ..Default::default()
+        });
+        let spotify = SpotifyBuilder::default()
+            .token(tok)
+            .retry_policy(policy)
+            .build()
+            .unwrap();
+        let spotify = Spotify {
+            http: fake.clone(),
+            ..spotify
+        };
+
+        (spotify, fake)
+    }
+
+    #[maybe_async]
+    #[cfg_attr(feature = "__async", tokio::test)]
+    #[cfg_attr(feature = "__sync", test)]
+    async fn test_custom_client() {
+        let (spotify, fake) = client(vec![response(503, ""), response(200, "{}")]);
+        let mut query = Query::new();
+        query.insert("market".to_owned(), "ES".to_owned());
+
+        let body = spotify.get("me", None, &query).await.unwrap();
+        assert_eq!(body, "{}");
+        assert_eq!(*fake.sleeps.lock().unwrap(), vec![Duration::from_secs(1)]);
+
+        let requests = fake.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].method, Method::Get);
+        assert_eq!(requests[1].url, "https://api.spotify.com/v1/me");
+        assert_eq!(requests[1].query, query);
+        assert_eq!(
+            requests[1].headers["authorization"],
+            "Bearer test-access-token"
+        );
+    }
+
+    #[maybe_async]
+    #[cfg_attr(feature = "__async", tokio::test)]
+    #[cfg_attr(feature = "__sync", test)]
+    async fn test_non_idempotent_retries() {
+        let responses = vec![response(502, ""), response(503, ""), response(201, "{}")];
+        let (spotify, fake) = client(responses);
+
+        // A POST may have been processed despite the 502, so it isn't retried
+        let result = spotify.post("me/player/queue", None, &Value::Null).await;
+        assert!(result.is_err());
+        assert_eq!(fake.requests.lock().unwrap().len(), 1);
+
+        // But it's retried with a 503
+        let body = spotify.post("me/player/queue", None, &Value::Null).await;
+        assert_eq!(body.unwrap(), "{}");
+        assert_eq!(fake.requests.lock().unwrap().len(), 3);
+        assert_eq!(*fake.sleeps.lock().unwrap(), vec![Duration::from_secs(1)]);
+    }
+
+    #[maybe_async]
+    #[cfg_attr(feature = "__async", tokio::test)]
+    #[cfg_attr(feature = "__sync", test)]
+    async fn test_error_response() {
+        let body = r#"{"error": {"status": 404, "message": "Not found"}}"#;
+        let (spotify, _) = client(vec![response(404, body), response(401, "")]);
+
+        let result = spotify.get("me", None, &Query::new()).await;
+        assert!(matches!(
+            result,
+            Err(ClientError::API(APIError::Regular { status: 404, .. }))
+        ));
+        let result = spotify.delete("me", None, &Value::Null).await;
+        assert!(matches!(result, Err(ClientError::Unauthorized)));
+    }
+
+    #[maybe_async]
+    #[cfg_attr(feature = "__async", tokio::test)]
+    #[cfg_attr(feature = "__sync", test)]
+    async fn test_response_metadata() {
+        let mut ok = response(200, "{}");
+        ok.headers.insert("etag".to_owned(), r#""abc""#.to_owned());
+        let body = r#"{"error": {"status": 404, "message": "Not found"}}"#;
+        let (spotify, fake) = client(vec![ok, response(404, body), response(304, "")]);
+        assert_eq!(spotify.last_response(), None);
+
+        let spotify = spotify.with_response_metadata();
+        spotify.get("me", None, &Query::new()).await.unwrap();
+        let metadata = spotify.last_response().unwrap();
+        assert_eq!(metadata.status, 200);
+        assert_eq!(metadata.etag(), Some(r#""abc""#));
+
+        let result = spotify.get("me", None, &Query::new()).await;
+        assert!(result.is_err());
+        assert_eq!(spotify.last_response().unwrap().status, 404);
+
+        // Conditional requests aren't considered errors
+        let mut headers = Headers::new();
+        headers.insert("If-None-Match".to_owned(), r#""abc""#.to_owned());
+        let response = spotify
+            .api_response(Method::Get, "me", &headers, &Query::new(), Body::Empty)
+            .await
+            .unwrap();
+        assert_eq!(response.status, 304);
+
+        let requests = fake.requests.lock().unwrap();
+        assert_eq!(requests[2].headers["if-none-match"], r#""abc""#);
+        assert_eq!(
+            requests[2].headers["authorization"],
+            "Bearer test-access-token"
+        );
+    }
+
+    #[maybe_async]
+    #[cfg_attr(feature = "__async", tokio::test)]
+    #[cfg_attr(feature = "__sync", test)]
+    async fn test_response_cache() {
+        let mut ok = response(200, r#"{"name": "cached"}"#);
+        ok.headers.insert("etag".to_owned(), r#""abc""#.to_owned());
+        let responses = vec![ok, response(304, ""), response(200, "{}")];
+        let (spotify, fake) = client(responses);
+        let spotify = Spotify {
+            response_cache: Some(Arc::new(MemoryResponseCache::default())),
+            ..spotify
+        };
+        let mut query = Query::new();
+        query.insert("market".to_owned(), "ES".to_owned());
+
+        let body = spotify.get("me", None, &query).await.unwrap();
+        assert_eq!(body, r#"{"name": "cached"}"#);
+        let body = spotify.get("me", None, &query).await.unwrap();
+        assert_eq!(body, r#"{"name": "cached"}"#);
+        // Other queries aren't cached
+        let body = spotify.get("me", None, &Query::new()).await.unwrap();
+        assert_eq!(body, "{}");
+
+        let requests = fake.requests.lock().unwrap();
+        assert!(!requests[0].headers.contains_key("if-none-match"));
+        assert_eq!(requests[1].headers["if-none-match"], r#""abc""#);
+        assert!(!requests[2].headers.contains_key("if-none-match"));
+    }
+
+    #[maybe_async]
+    #[cfg_attr(feature = "__async", tokio::test)]
+    #[cfg_attr(feature = "__sync", test)]
+    async fn test_rate_limit() {
+        let (spotify, fake) = client(vec![response(200, "{}"), response(200, "{}")]);
+        let limit = RateLimitBuilder::default()
+            .requests_per_second(1.0)
+            .max_concurrent(1)
+            .build()
+            .unwrap();
+        let limiter = Arc::new(RateLimiter::new(limit));
+        let spotify = Spotify {
+            rate_limiter: Some(limiter.clone()),
+            ..spotify
+        };
+
+        spotify.get("me", None, &Query::new()).await.unwrap();
+        // Clones share the same limiter
+        spotify
+            .clone()
+            .get("me", None, &Query::new())
+            .await
+            .unwrap();
+
+        let sleeps = fake.sleeps.lock().unwrap();
+        assert_eq!(sleeps.len(), 1);
+        assert!(sleeps[0] > Duration::from_millis(900));
+        let metrics = limiter.metrics();
+        assert_eq!(metrics.requests, 2);
+        assert_eq!(metrics.throttled, 1);
+        assert!(metrics.total_wait >= sleeps[0]);
+    }
+
+    #[test]
+    fn test_classify_response() {
+        let body = r#"{"error": {"status": 403, "message": "Player command failed: Premium required", "reason": "PREMIUM_REQUIRED"}}"#;
+        match ClientError::from_response(response(403, body)) {
+            ClientError::API(APIError::Player { status, reason, .. }) => {
+                assert_eq!(status, 403);
+                assert_eq!(reason, PlayerErrorReason::PremiumRequired);
+            }
+            err => panic!("unexpected error: {:?}", err),
+        }
+
+        let mut rate_limited = response(429, "");
+        rate_limited
+            .headers
+            .insert("retry-after".to_owned(), "5".to_owned());
+        assert!(matches!(
+            ClientError::from_response(rate_limited),
+            ClientError::RateLimited(Some(5))
+        ));
+
+        // Bodies without an error object are kept as they are
+        assert!(matches!(
+            ClientError::from_response(response(403, "Forbidden")),
+            ClientError::StatusCode(403, body) if body == "Forbidden"
+        ));
+        assert!(matches!(
+            ClientError::from_response(response(500, "")),
+            ClientError::StatusCode(500, _)
+        ));
+    }
+
+    #[maybe_async]
+    #[cfg_attr(feature = "__async", tokio::test)]
+    #[cfg_attr(feature = "__sync", test)]
+    async fn test_timeout() {
+        // The connection is accepted by the OS, but nothing ever answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let prefix = format!("http://{}/", listener.local_addr().unwrap());
+        let tok = TokenBuilder::default()
+            .access_token("test-access-token")
+            .expires_in(3600)
+            .expires_at(i64::MAX)
+            .build()
+            .unwrap();
+        let spotify = SpotifyBuilder::default()
+            .token(tok)
+            .prefix(prefix)
+            .build()
+            .unwrap();
+
+        let result = spotify
+            .with_timeout(Duration::from_millis(100))
+            .get("me", None, &Query::new())
+            .await;
+        assert!(matches!(result, Err(ClientError::Timeout)));
+    }
+
+    #[test]
+    fn test_http_config() {
+        let config = HttpConfigBuilder::default()
+            .timeout(Duration::from_secs(10))
+            .user_agent("rspotify-test")
+            .proxy("http://localhost:3128")
+            .build()
+            .unwrap();
+        assert!(SpotifyBuilder::default()
+            .http_config(config)
+            .build()
+            .is_ok());
+
+        let config = HttpConfigBuilder::default()
+            .proxy("ftp://localhost:3128")
+            .build()
+            .unwrap();
+        assert!(SpotifyBuilder::default()
+            .http_config(config)
+            .build()
+            .is_err());
+    }
 }
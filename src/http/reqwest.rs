@@ -2,148 +2,118 @@
 //! default.
 
 use maybe_async::async_impl;
-use reqwest::{Method, RequestBuilder, StatusCode};
-use serde_json::Value;
 
 use std::convert::TryInto;
+use std::time::Duration;
 
-use super::{headers, BaseClient, Form, Headers, Query};
-use crate::client::{APIError, ClientError, ClientResult, Spotify};
+use super::{Body, HttpClient, HttpConfig, HttpRequest, HttpResponse, Method};
+use crate::client::{ClientError, ClientResult};
 
-impl ClientError {
-    pub async fn from_response(response: reqwest::Response) -> Self {
-        match response.status() {
-            StatusCode::UNAUTHORIZED => Self::Unauthorized,
-            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited(
-                response
-                    .headers()
-                    .get(reqwest::header::RETRY_AFTER)
-                    .and_then(|header| header.to_str().ok())
-                    .and_then(|duration| duration.parse().ok()),
-            ),
-            status @ StatusCode::FORBIDDEN | status @ StatusCode::NOT_FOUND => response
-                .json::<APIError>()
-                .await
-                .map(Into::into)
-                .unwrap_or_else(|_| status.into()),
-            status => status.into(),
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            Self::Timeout
+        } else {
+            Self::Request(err.to_string())
         }
     }
 }
 
-impl From<reqwest::Error> for ClientError {
-    fn from(err: reqwest::Error) -> Self {
-        Self::Request(err.to_string())
-    }
+/// The [`HttpClient`](trait.HttpClient.html) for [`reqwest`
+/// ](https://docs.rs/reqwest), used by default with the `client-reqwest`
+/// feature. A preconfigured `reqwest::Client` can be used with `From`.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestClient {
+    /// reqwest needs an instance of its client to perform requests.
+    client: reqwest::Client,
 }
 
-impl From<reqwest::StatusCode> for ClientError {
-    fn from(code: reqwest::StatusCode) -> Self {
-        Self::StatusCode(
-            code.as_u16(),
-            code.canonical_reason().unwrap_or("unknown").to_string(),
-        )
+impl ReqwestClient {
+    /// Builds a client with the given configuration, which fails if the
+    /// proxy URL is invalid.
+    pub fn new(config: &HttpConfig) -> ClientResult<Self> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(timeout) = config.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = config.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(ref user_agent) = config.user_agent {
+            builder = builder.user_agent(user_agent.as_str());
+        }
+        if let Some(ref proxy) = config.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy.as_str())?);
+        }
+
+        Ok(ReqwestClient {
+            client: builder.build()?,
+        })
     }
 }
 
-impl Spotify {
-    async fn request<D>(
-        &self,
-        method: Method,
-        url: &str,
-        headers: Option<&Headers>,
-        add_data: D,
-    ) -> ClientResult<String>
-    where
-        D: Fn(RequestBuilder) -> RequestBuilder,
-    {
-        let url = self.endpoint_url(url);
+impl From<reqwest::Client> for ReqwestClient {
+    fn from(client: reqwest::Client) -> Self {
+        ReqwestClient { client }
+    }
+}
 
-        // The default auth headers are used if none were specified.
-        let mut auth;
-        let headers = match headers {
-            Some(h) => h,
-            None => {
-                auth = Headers::new();
-                let (key, val) = headers::bearer_auth(self.get_token()?);
-                auth.insert(key, val);
-                &auth
-            }
+#[async_impl]
+impl HttpClient for ReqwestClient {
+    async fn send(&self, request: &HttpRequest) -> ClientResult<HttpResponse> {
+        let method = match request.method {
+            Method::Get => reqwest::Method::GET,
+            Method::Post => reqwest::Method::POST,
+            Method::Put => reqwest::Method::PUT,
+            Method::Delete => reqwest::Method::DELETE,
         };
+
         // The headers need to be converted into a `reqwest::HeaderMap`, which
-        // won't fail as long as its contents are ASCII. This is an internal
-        // function, so the condition will always be true.
+        // fails for invalid names or values, like the ones with line breaks
+        // passed to `Spotify::api_response`.
         //
         // The content-type header will be set automatically.
-        let headers = headers.try_into().unwrap();
+        let headers: reqwest::header::HeaderMap = (&request.headers)
+            .try_into()
+            .map_err(|err| ClientError::Request(format!("invalid header: {}", err)))?;
 
-        let mut request = self.client.request(method.clone(), &url).headers(headers);
-        request = add_data(request);
-        log::info!("Making request {:?}", request);
-        let response = request.send().await?;
-
-        if response.status().is_success() {
-            response.text().await.map_err(Into::into)
-        } else {
-            Err(ClientError::from_response(response).await)
+        let mut builder = self
+            .client
+            .request(method, &request.url)
+            .headers(headers)
+            .query(&request.query);
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
         }
-    }
-}
-
-#[async_impl]
-impl BaseClient for Spotify {
-    #[inline]
-    async fn get(
-        &self,
-        url: &str,
-        headers: Option<&Headers>,
-        payload: &Query,
-    ) -> ClientResult<String> {
-        self.request(Method::GET, url, headers, |req| req.query(payload))
-            .await
-    }
-
-    #[inline]
-    async fn post(
-        &self,
-        url: &str,
-        headers: Option<&Headers>,
-        payload: &Value,
-    ) -> ClientResult<String> {
-        self.request(Method::POST, url, headers, |req| req.json(payload))
-            .await
-    }
+        builder = match request.body {
+            Body::Empty => builder,
+            Body::Json(ref json) => builder.json(json),
+            Body::Form(ref form) => builder.form(form),
+            Body::Raw {
+                ref content_type,
+                ref data,
+            } => builder
+                .header(reqwest::header::CONTENT_TYPE, content_type.as_str())
+                .body(data.clone()),
+        };
 
-    #[inline]
-    async fn post_form(
-        &self,
-        url: &str,
-        headers: Option<&Headers>,
-        payload: &Form,
-    ) -> ClientResult<String> {
-        self.request(Method::POST, url, headers, |req| req.form(payload))
-            .await
-    }
+        let response = builder.send().await?;
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(key, val)| Some((key.as_str().to_owned(), val.to_str().ok()?.to_owned())))
+            .collect();
+        let body = response.text().await?;
 
-    #[inline]
-    async fn put(
-        &self,
-        url: &str,
-        headers: Option<&Headers>,
-        payload: &Value,
-    ) -> ClientResult<String> {
-        self.request(Method::PUT, url, headers, |req| req.json(payload))
-            .await
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
     }
 
-    #[inline]
-    async fn delete(
-        &self,
-        url: &str,
-        headers: Option<&Headers>,
-        payload: &Value,
-    ) -> ClientResult<String> {
-        self.request(Method::DELETE, url, headers, |req| req.json(payload))
-            .await
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::delay_for(duration).await
     }
 }
@@ -1,108 +1,154 @@
 //! The client implementation for the ureq HTTP client, which is blocking.
 
-use super::{headers, BaseClient, Form, Headers, Query};
-use crate::client::{ClientError, ClientResult, Spotify};
+use super::{Body, HttpClient, HttpConfig, HttpRequest, HttpResponse, Method};
+use crate::client::{ClientError, ClientResult};
 
 use maybe_async::sync_impl;
-use serde_json::Value;
-use ureq::{Request, Response};
 
-impl ClientError {
-    pub fn from_response(r: ureq::Response) -> Self {
-        ClientError::StatusCode(r.status(), r.status_text().to_string())
-    }
+use std::io;
+use std::time::Duration;
+
+/// The [`HttpClient`](trait.HttpClient.html) for [`ureq`
+/// ](https://docs.rs/ureq), used by default with the `client-ureq` feature.
+/// A preconfigured `ureq::Agent` can be used with `From`.
+#[derive(Debug, Clone, Default)]
+pub struct UreqClient {
+    /// The agent keeps the connection pool and the common configuration.
+    agent: ureq::Agent,
+    // ureq sets the timeouts per request instead of in the agent.
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
 }
 
-impl Spotify {
-    fn request<D>(
-        &self,
-        request: &mut Request,
-        headers: Option<&Headers>,
-        send_request: D,
-    ) -> ClientResult<String>
-    where
-        D: Fn(&mut Request) -> Response,
-    {
-        // Setting the headers, which will be the token auth if unspecified.
-        match headers {
-            Some(headers) => {
-                for (key, val) in headers.iter() {
-                    request.set(&key, &val);
-                }
-            }
-            None => {
-                let (key, val) = headers::bearer_auth(self.get_token()?);
-                request.set(&key, &val);
-            }
+impl UreqClient {
+    /// Builds a client with the given configuration, which fails if the
+    /// proxy URL is invalid.
+    pub fn new(config: &HttpConfig) -> ClientResult<Self> {
+        let mut agent = ureq::agent();
+        if let Some(ref user_agent) = config.user_agent {
+            agent.set("User-Agent", user_agent);
+        }
+        if let Some(ref proxy) = config.proxy {
+            let proxy = ureq::Proxy::new(proxy)
+                .map_err(|err| ClientError::Request(format!("invalid proxy: {}", err)))?;
+            agent.set_proxy(proxy);
         }
 
-        log::info!("Making request {:?}", request);
-        let response = send_request(request);
+        Ok(UreqClient {
+            agent,
+            timeout: config.timeout,
+            connect_timeout: config.connect_timeout,
+        })
+    }
+}
 
-        if response.ok() {
-            response.into_string().map_err(Into::into)
-        } else {
-            Err(ClientError::from_response(response))
+impl From<ureq::Agent> for UreqClient {
+    fn from(agent: ureq::Agent) -> Self {
+        UreqClient {
+            agent,
+            ..Default::default()
         }
     }
 }
 
-#[sync_impl]
-impl BaseClient for Spotify {
-    #[inline]
-    fn get(&self, url: &str, headers: Option<&Headers>, payload: &Query) -> ClientResult<String> {
-        self.request(
-            &mut ureq::get(&self.endpoint_url(url)),
-            headers,
-            |mut req| {
-                for (key, val) in payload.iter() {
-                    req = req.query(&key, &val)
-                }
-                req.call()
-            },
-        )
-    }
+/// ureq reports the timeouts as I/O errors, which may also be `WouldBlock`
+/// depending on the platform.
+fn is_timeout(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+    )
+}
 
-    #[inline]
-    fn post(&self, url: &str, headers: Option<&Headers>, payload: &Value) -> ClientResult<String> {
-        self.request(&mut ureq::post(&self.endpoint_url(url)), headers, |req| {
-            req.send_json(payload.clone())
-        })
+/// ureq writes the headers as they are, so the invalid ones are rejected
+/// here, like reqwest does. Otherwise, a value with a line break passed to
+/// `Spotify::api_response` would inject other headers into the request.
+fn check_header(key: &str, val: &str) -> ClientResult<()> {
+    let valid_key = !key.is_empty()
+        && key
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&c));
+    let valid_val = val.bytes().all(|c| c == b'\t' || (c >= b' ' && c != 0x7f));
+    if valid_key && valid_val {
+        Ok(())
+    } else {
+        Err(ClientError::Request(format!("invalid header: {}", key)))
     }
+}
 
-    #[inline]
-    fn post_form(
-        &self,
-        url: &str,
-        headers: Option<&Headers>,
-        payload: &Form,
-    ) -> ClientResult<String> {
-        let payload = payload
-            .iter()
-            .map(|(key, val)| (key.as_str(), val.as_str()))
-            .collect::<Vec<_>>();
+#[sync_impl]
+impl HttpClient for UreqClient {
+    fn send(&self, request: &HttpRequest) -> ClientResult<HttpResponse> {
+        let method = match request.method {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+        };
 
-        self.request(&mut ureq::post(&self.endpoint_url(url)), headers, |req| {
-            req.send_form(&payload)
-        })
-    }
+        let mut req = self.agent.request(method, &request.url);
+        if let Some(timeout) = request.timeout.or(self.timeout) {
+            req.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            req.timeout_connect(timeout.as_millis() as u64);
+        }
+        for (key, val) in request.headers.iter() {
+            check_header(key, val)?;
+            req.set(key, val);
+        }
+        for (key, val) in request.query.iter() {
+            req.query(key, val);
+        }
+        let response = match request.body {
+            Body::Empty => req.call(),
+            Body::Json(ref json) => req.send_json(json.clone()),
+            Body::Form(ref form) => {
+                let form = form
+                    .iter()
+                    .map(|(key, val)| (key.as_str(), val.as_str()))
+                    .collect::<Vec<_>>();
+                req.send_form(&form)
+            }
+            Body::Raw {
+                ref content_type,
+                ref data,
+            } => req.set("Content-Type", content_type).send_string(data),
+        };
+
+        // ureq returns the errors that prevented the request from being
+        // completed as synthetic responses.
+        match response.synthetic_error() {
+            Some(ureq::Error::Io(err)) if is_timeout(err) => return Err(ClientError::Timeout),
+            Some(err) => return Err(ClientError::Request(err.to_string())),
+            None => (),
+        }
 
-    #[inline]
-    fn put(&self, url: &str, headers: Option<&Headers>, payload: &Value) -> ClientResult<String> {
-        self.request(&mut ureq::put(&self.endpoint_url(url)), headers, |req| {
-            req.send_json(payload.clone())
+        let status = response.status();
+        let headers = response
+            .headers_names()
+            .into_iter()
+            .filter_map(|key| {
+                let val = response.header(&key)?.to_owned();
+                Some((key.to_lowercase(), val))
+            })
+            .collect();
+        let body = response.into_string().map_err(|err| {
+            if is_timeout(&err) {
+                ClientError::Timeout
+            } else {
+                err.into()
+            }
+        })?;
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
         })
     }
 
-    #[inline]
-    fn delete(
-        &self,
-        url: &str,
-        headers: Option<&Headers>,
-        payload: &Value,
-    ) -> ClientResult<String> {
-        self.request(&mut ureq::delete(&self.endpoint_url(url)), headers, |req| {
-            req.send_json(payload.clone())
-        })
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration)
     }
 }
@@ -51,10 +51,34 @@
 //! }
 //! ```
 //!
-//! [`reqwest`](https://docs.rs/reqwest/#proxies) supports system proxies by
-//! default. It reads the environment variables `HTTP_PROXY` and `HTTPS_PROXY`
+//! Any other HTTP client can be used by implementing the [`HttpClient`
+//! ](http/trait.HttpClient.html) trait for it and passing it to
+//! [`SpotifyBuilder::http_client`
+//! ](client/struct.SpotifyBuilder.html#method.http_client). One of the
+//! features above is still needed to choose whether Rspotify is asynchronous
+//! or blocking, which the client has to match.
+//!
+//! The timeouts, user agent and proxy of the included clients can be set with
+//! [`SpotifyBuilder::http_config`
+//! ](client/struct.SpotifyBuilder.html#method.http_config), or a
+//! preconfigured client can be reused with `SpotifyBuilder::reqwest_client` or
+//! `SpotifyBuilder::ureq_agent`. Additionally, [`reqwest`
+//! ](https://docs.rs/reqwest/#proxies) supports system proxies by default. It
+//! reads the environment variables `HTTP_PROXY` and `HTTPS_PROXY`
 //! environmental variables to set HTTP and HTTPS proxies, respectively.
 //!
+//! The URLs of the Spotify Web API and of the Accounts service, which handles
+//! the authorization, can be changed with [`SpotifyBuilder::prefix`
+//! ](client/struct.SpotifyBuilder.html#method.prefix) and
+//! [`SpotifyBuilder::auth_prefix`
+//! ](client/struct.SpotifyBuilder.html#method.auth_prefix), respectively. This
+//! is useful for going through a proxy, or for testing against a local server
+//! that stands in for Spotify.
+//!
+//! The `mock-server` feature adds a mock Spotify server in the [`mock`
+//! ](mock/index.html) module, so that applications using Rspotify can be
+//! tested offline.
+//!
 //! Rspotify supports the [`dotenv` crate
 //! ](https://github.com/dotenv-rs/dotenv), which allows you to save
 //! credentials in a `.env` file. These will then be available as environmental
@@ -100,8 +124,9 @@
 //! 1. The user logs in with the request URL, which redirects to the redirect
 //!    URI and provides a code in the parameters. This happens on your side.
 //! 2. The code obtained in the previous step is parsed with
-//!    [`Spotify::parse_response_code`
-//!    ](client/struct.Spotify.html#method.parse_response_code).
+//!    [`Spotify::parse_authorization_response`
+//!    ](client/struct.Spotify.html#method.parse_authorization_response),
+//!    which also verifies the state sent in the request URL.
 //! 3. The code is sent to Spotify in order to obtain an access token with
 //!    [`Spotify::request_user_token`
 //!    ](client/struct.Spotify.html#method.request_user_token) or
@@ -118,6 +143,31 @@
 //!    these steps, but the advantage of refreshing it is that this doesn't
 //!    require the user to log in, and that it's a simpler procedure.
 //!
+//! The scopes the user is asked to grant are set in [`OAuth::scope`
+//! ](oauth2/struct.OAuth.html#structfield.scope) with the typed [`Scope`
+//! ](scopes/enum.Scope.html)s. The endpoints that need specific scopes check
+//! them before the request, returning [`ClientError::MissingScopes`
+//! ](client/enum.ClientError.html#variant.MissingScopes) if the access token
+//! hasn't been granted them.
+//!
+//! Applications that can't store the client secret safely, like desktop or
+//! mobile apps, should follow the [Authorization Code Flow with PKCE
+//! ](https://developer.spotify.com/documentation/general/guides/authorization-guide/#authorization-code-flow-with-proof-key-for-code-exchange-pkce)
+//! instead, which works the same way with the `_pkce` variants of the methods
+//! above, like [`Spotify::get_authorize_url_pkce`
+//! ](client/struct.Spotify.html#method.get_authorize_url_pkce). The secret
+//! can then be left unset in the credentials.
+//!
+//! Clients that run entirely in the browser, without a server to request the
+//! token from, can follow the [Implicit Grant Flow
+//! ](https://developer.spotify.com/documentation/general/guides/authorization-guide/#implicit-grant-flow)
+//! with [`Spotify::get_authorize_url_implicit`
+//! ](client/struct.Spotify.html#method.get_authorize_url_implicit) and
+//! [`Spotify::parse_implicit_grant_response`
+//! ](client/struct.Spotify.html#method.parse_implicit_grant_response), which
+//! obtains the access token from the redirect directly. These tokens can't be
+//! refreshed.
+//!
 //! See the [`webapp`
 //! ](https://github.com/ramsayleung/rspotify/tree/master/examples/webapp)
 //! example for more details on how you can implement it for something like a
@@ -132,6 +182,12 @@
 //! by opening the request URL in its default browser, and the requests will be
 //! performed automatically.
 //!
+//! By default, the user then has to paste the URL they were redirected to.
+//! After enabling [`OAuth::callback_server`
+//! ](oauth2/struct.OAuth.html#structfield.callback_server), Rspotify instead
+//! listens on the redirect URI, which must point to the local machine, and
+//! obtains the code from the redirect automatically.
+//!
 //! An example of the CLI authentication:
 //!
 //! ![demo](https://raw.githubusercontent.com/ramsayleung/rspotify/master/doc/images/rspotify.gif)
@@ -154,10 +210,18 @@
 //! ](https://github.com/ramsayleung/rspotify/tree/master/examples)
 //! which can serve as a learning tool.
 
+pub mod cache;
 pub mod client;
-mod http;
+pub mod http;
+#[cfg(feature = "mock-server")]
+pub mod mock;
 pub mod model;
 pub mod oauth2;
+pub mod pagination;
+pub mod ratelimit;
+pub mod retry;
+pub mod scopes;
+pub mod store;
 pub mod util;
 
 #[doc(hidden)]
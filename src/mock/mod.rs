@@ -0,0 +1,554 @@
+//! A mock of the Spotify Web API and Accounts service, for testing the client
+//! offline. It's only available after enabling the `mock-server` feature.
+//!
+//! [`MockServer`](struct.MockServer.html) listens on a random port of
+//! localhost, and the client is pointed at it with `Spotify::prefix` and
+//! `Spotify::auth_prefix`. It serves canned responses for all the endpoints
+//! in the client, issues mock access tokens, and records the requests it
+//! receives so that they can be inspected afterwards:
+//!
+//! ```
+//! # #[cfg(feature = "client-ureq")]
+//! # fn main() {
+//! use rspotify::mock::MockServer;
+//! use rspotify::model::TrackId;
+//!
+//! let server = MockServer::start().unwrap();
+//! let mut spotify = server.client_builder().build().unwrap();
+//! spotify.request_client_token().unwrap();
+//!
+//! let id = TrackId::from_id("4iV5W9uYEdYUVa79Axb7Rh").unwrap();
+//! let track = spotify.track(&id).unwrap();
+//! assert_eq!(track.id.as_deref(), Some("4iV5W9uYEdYUVa79Axb7Rh"));
+//! assert_eq!(server.requests().last().unwrap().path, "/v1/tracks/4iV5W9uYEdYUVa79Axb7Rh");
+//! # }
+//! # #[cfg(not(feature = "client-ureq"))]
+//! # fn main() {}
+//! ```
+
+mod responses;
+
+pub use responses::MOCK_PAGE_TOTAL;
+
+use crate::client::{ClientResult, SpotifyBuilder};
+use crate::oauth2::CredentialsBuilder;
+use crate::store::MemoryTokenStore;
+
+use serde_json::{json, Value};
+use url::form_urlencoded;
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// The access token issued by the mock server.
+pub const MOCK_ACCESS_TOKEN: &str = "mock-access-token";
+/// The refresh token issued by the mock server.
+pub const MOCK_REFRESH_TOKEN: &str = "mock-refresh-token";
+/// The authorization code the mock server redirects to after authorizing.
+pub const MOCK_AUTH_CODE: &str = "mock-auth-code";
+
+/// A request received by the mock server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockRequest {
+    /// The HTTP method, like `GET`.
+    pub method: String,
+    /// The path of the request, without the query, like `/v1/me`.
+    pub path: String,
+    /// The parameters in the query of the request.
+    pub query: Vec<(String, String)>,
+    /// The headers of the request, with lowercase names.
+    pub headers: HashMap<String, String>,
+    /// The body of the request.
+    pub body: String,
+}
+
+impl MockRequest {
+    /// Returns the value of a parameter in the query, if present.
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the value of a parameter in the body, if it's a form.
+    pub fn form_param(&self, key: &str) -> Option<String> {
+        form_urlencoded::parse(self.body.as_bytes())
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.into_owned())
+    }
+
+    /// Returns the body parsed as JSON, or `Value::Null` if it isn't valid.
+    pub fn json(&self) -> Value {
+        serde_json::from_str(&self.body).unwrap_or(Value::Null)
+    }
+
+    /// The comma-separated IDs in the `ids` parameter of the query.
+    fn ids(&self) -> Vec<&str> {
+        self.param("ids")
+            .map(|ids| ids.split(',').filter(|id| !id.is_empty()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Reads the request from a connection, returning `None` if it's
+    /// malformed.
+    fn read(stream: &TcpStream) -> Option<Self> {
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let mut parts = line.split_whitespace();
+        let method = parts.next()?.to_owned();
+        let target = parts.next()?.to_owned();
+
+        let mut headers = HashMap::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).ok()?;
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line.split_at(line.find(':')?);
+            headers.insert(name.to_lowercase(), value[1..].trim().to_owned());
+        }
+
+        let length = headers
+            .get("content-length")
+            .and_then(|length| length.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).ok()?;
+
+        let (path, query) = match target.find('?') {
+            Some(index) => target.split_at(index),
+            None => (target.as_str(), ""),
+        };
+        let query = form_urlencoded::parse(query.trim_start_matches('?').as_bytes())
+            .into_owned()
+            .collect();
+
+        Some(MockRequest {
+            method,
+            path: path.to_owned(),
+            query,
+            headers,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        })
+    }
+}
+
+/// A response of the mock server.
+struct MockResponse {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: Option<Value>,
+}
+
+impl MockResponse {
+    fn json(status: u16, body: Value) -> Self {
+        MockResponse {
+            status,
+            headers: Vec::new(),
+            body: Some(body),
+        }
+    }
+
+    fn ok(body: Value) -> Self {
+        Self::json(200, body)
+    }
+
+    fn empty(status: u16) -> Self {
+        MockResponse {
+            status,
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(
+            status,
+            json!({ "error": { "status": status, "message": message } }),
+        )
+    }
+
+    fn write(self, mut stream: &TcpStream) -> std::io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            201 => "Created",
+            204 => "No Content",
+            302 => "Found",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            _ => "Not Found",
+        };
+        let body = self.body.map(|body| body.to_string()).unwrap_or_default();
+
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason);
+        for (name, value) in self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        if !body.is_empty() {
+            head.push_str("Content-Type: application/json\r\n");
+        }
+        head.push_str(&format!("Content-Length: {}\r\n", body.len()));
+        head.push_str("Connection: close\r\n\r\n");
+
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(body.as_bytes())?;
+        stream.flush()
+    }
+}
+
+/// A mock Spotify server running in the background, which is stopped when
+/// dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts the server on a random port of localhost.
+    pub fn start() -> ClientResult<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = {
+            let requests = Arc::clone(&requests);
+            let running = Arc::clone(&running);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if !running.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        let requests = Arc::clone(&requests);
+                        thread::spawn(move || handle(stream, &requests));
+                    }
+                }
+            })
+        };
+
+        Ok(MockServer {
+            addr,
+            requests,
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    /// The base URL of the server, like `http://127.0.0.1:1234/`.
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.addr)
+    }
+
+    /// The URL to use as `Spotify::prefix`.
+    pub fn api_prefix(&self) -> String {
+        self.url() + "v1/"
+    }
+
+    /// The URL to use as `Spotify::auth_prefix`.
+    pub fn auth_prefix(&self) -> String {
+        self.url()
+    }
+
+    /// A client builder pointed at the server, with mock credentials and a
+    /// [`MemoryTokenStore`](../store/struct.MemoryTokenStore.html), so that
+    /// no cache file is written. A token still has to be requested, or
+    /// configured with `SpotifyBuilder::token`.
+    pub fn client_builder(&self) -> SpotifyBuilder {
+        let creds = CredentialsBuilder::default()
+            .id("mock-client-id")
+            .secret("mock-client-secret")
+            .build()
+            .unwrap();
+
+        let mut builder = SpotifyBuilder::default();
+        builder
+            .prefix(self.api_prefix())
+            .auth_prefix(self.auth_prefix())
+            .credentials(creds)
+            .token_store(MemoryTokenStore::new());
+        builder
+    }
+
+    /// Returns the requests received so far, in order.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Forgets the requests received so far.
+    pub fn clear_requests(&self) {
+        self.requests.lock().unwrap().clear();
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        // The listener is blocked waiting for a connection, so one is made
+        // for it to notice that it has to stop.
+        self.running.store(false, Ordering::SeqCst);
+        if TcpStream::connect(self.addr).is_ok() {
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+/// Answers a single request, saving it first.
+fn handle(stream: TcpStream, requests: &Mutex<Vec<MockRequest>>) {
+    if let Some(request) = MockRequest::read(&stream) {
+        log::info!("Mock server received {} {}", request.method, request.path);
+        let response = route(&request);
+        requests.lock().unwrap().push(request);
+        let _ = response.write(&stream);
+    }
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+/// Chooses the response for a request.
+fn route(request: &MockRequest) -> MockResponse {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/authorize") => return authorize(request),
+        ("POST", "/api/token") => return token(request),
+        _ => {}
+    }
+
+    let path = match request.path.strip_prefix("/v1/") {
+        Some(path) => path,
+        None => return MockResponse::error(404, "Service not found"),
+    };
+    let authorized = matches!(
+        request.headers.get("authorization"),
+        Some(auth) if auth.starts_with("Bearer ")
+    );
+    if !authorized {
+        return MockResponse::error(401, "No token provided");
+    }
+
+    api(
+        request,
+        &path
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// The Accounts service redirects to the redirect URI with an authorization
+/// code right away, as if the user had logged in.
+fn authorize(request: &MockRequest) -> MockResponse {
+    let redirect_uri = match request.param("redirect_uri") {
+        Some(redirect_uri) => redirect_uri,
+        None => return MockResponse::error(400, "Missing redirect_uri"),
+    };
+    let mut location = format!("{}?code={}", redirect_uri, MOCK_AUTH_CODE);
+    if let Some(state) = request.param("state") {
+        location.push_str(&format!("&state={}", state));
+    }
+
+    MockResponse {
+        status: 302,
+        headers: vec![("Location", location)],
+        body: None,
+    }
+}
+
+/// Issues a token for any of the grant types supported by the client.
+fn token(request: &MockRequest) -> MockResponse {
+    let grant_type = request.form_param("grant_type").unwrap_or_default();
+    let has_client =
+        request.headers.contains_key("authorization") || request.form_param("client_id").is_some();
+    if !has_client {
+        return MockResponse::json(400, json!({ "error": "invalid_client" }));
+    }
+
+    let mut token = json!({
+        "access_token": MOCK_ACCESS_TOKEN,
+        "token_type": "Bearer",
+        "expires_in": 3600,
+        "scope": request.form_param("scope").unwrap_or_default(),
+    });
+    match grant_type.as_str() {
+        "client_credentials" => {}
+        "authorization_code" | "refresh_token" => {
+            token["refresh_token"] = json!(MOCK_REFRESH_TOKEN);
+        }
+        _ => return MockResponse::json(400, json!({ "error": "unsupported_grant_type" })),
+    }
+
+    MockResponse::ok(token)
+}
+
+/// The endpoints of the Web API, given the segments of the path after
+/// `/v1/`.
+fn api(request: &MockRequest, path: &[&str]) -> MockResponse {
+    use responses::*;
+
+    let ids = request.ids();
+    let many = |item: fn(&str) -> Value| ids.iter().map(|id| item(id)).collect::<Vec<_>>();
+    let paged = |prefix, item| page(request, prefix, item);
+    let contains = || MockResponse::ok(json!(vec![true; ids.len()]));
+
+    // Spotify rejects the requests with more IDs than allowed at once
+    let max_ids = match path {
+        ["albums"] | ["me", "albums", "contains"] => 20,
+        ["audio-features"] => 100,
+        _ => 50,
+    };
+    if ids.len() > max_ids {
+        return MockResponse::error(400, "Too many ids requested");
+    }
+
+    let response = match (request.method.as_str(), path) {
+        // Tracks, artists and albums
+        ("GET", ["tracks"]) => json!({ "tracks": many(full_track) }),
+        ("GET", ["tracks", id]) => full_track(id),
+        ("GET", ["artists"]) => json!({ "artists": many(full_artist) }),
+        ("GET", ["artists", id]) => full_artist(id),
+        ("GET", ["artists", _, "albums"]) => paged("mockalbum", simplified_album),
+        ("GET", ["artists", _, "top-tracks"]) => {
+            json!({ "tracks": [full_track("mocktrack0"), full_track("mocktrack1")] })
+        }
+        ("GET", ["artists", _, "related-artists"]) => {
+            json!({ "artists": [full_artist("mockartist0"), full_artist("mockartist1")] })
+        }
+        ("GET", ["albums"]) => json!({ "albums": many(full_album) }),
+        ("GET", ["albums", id]) => full_album(id),
+        ("GET", ["albums", _, "tracks"]) => paged("mocktrack", simplified_track),
+        ("GET", ["search"]) => search(request),
+
+        // Users and playlists
+        ("GET", ["users", id]) => public_user(id),
+        ("GET", ["users", _, "playlists"]) | ("GET", ["me", "playlists"]) => {
+            paged("mockplaylist", simplified_playlist)
+        }
+        ("POST", ["users", _, "playlists"]) => {
+            return MockResponse::json(201, full_playlist("mockplaylist"))
+        }
+        ("GET", ["users", _, "playlists", id]) | ("GET", ["playlists", id]) => full_playlist(id),
+        ("GET", ["users", _, "starred"]) => full_playlist("starred"),
+        ("PUT", ["playlists", _]) => return MockResponse::empty(200),
+        ("GET", ["playlists", _, "tracks"]) => paged("mocktrack", playlist_item),
+        ("POST", ["playlists", _, "tracks"]) => return MockResponse::json(201, playlist_result()),
+        ("PUT", ["playlists", _, "tracks"]) | ("DELETE", ["playlists", _, "tracks"]) => {
+            playlist_result()
+        }
+        ("PUT", ["playlists", _, "followers"]) | ("DELETE", ["playlists", _, "followers"]) => {
+            return MockResponse::empty(200)
+        }
+        ("GET", ["playlists", _, "followers", "contains"]) => return contains(),
+        ("GET", ["playlists", _, "images"]) => images(),
+        ("PUT", ["playlists", _, "images"]) => return MockResponse::empty(202),
+
+        // Library and personalization
+        ("GET", ["me"]) => private_user(),
+        ("GET", ["me", "albums"]) => paged("mockalbum", saved_album),
+        ("GET", ["me", "tracks"]) => paged("mocktrack", saved_track),
+        ("GET", ["me", "shows"]) => paged("mockshow", saved_show),
+        ("PUT", ["me", "albums"])
+        | ("DELETE", ["me", "albums"])
+        | ("PUT", ["me", "tracks"])
+        | ("DELETE", ["me", "tracks"])
+        | ("PUT", ["me", "shows"])
+        | ("DELETE", ["me", "shows"]) => return MockResponse::empty(200),
+        ("GET", ["me", "albums", "contains"])
+        | ("GET", ["me", "tracks", "contains"])
+        | ("GET", ["me", "shows", "contains"])
+        | ("GET", ["me", "following", "contains"]) => return contains(),
+        ("GET", ["me", "following"]) => {
+            json!({ "artists": cursor_page(request, "after", "mockartist", full_artist) })
+        }
+        ("PUT", ["me", "following"]) | ("DELETE", ["me", "following"]) => {
+            return MockResponse::empty(204)
+        }
+        ("GET", ["me", "top", "artists"]) => paged("mockartist", full_artist),
+        ("GET", ["me", "top", "tracks"]) => paged("mocktrack", full_track),
+
+        // Browse
+        ("GET", ["browse", "featured-playlists"]) => json!({
+            "message": "Mock featured playlists",
+            "playlists": paged("mockplaylist", simplified_playlist)
+        }),
+        ("GET", ["browse", "new-releases"]) => {
+            json!({ "albums": paged("mockalbum", simplified_album) })
+        }
+        ("GET", ["browse", "categories"]) => {
+            json!({ "categories": paged("mockcategory", category) })
+        }
+        ("GET", ["browse", "categories", id]) => category(id),
+        ("GET", ["browse", "categories", _, "playlists"]) => {
+            json!({ "playlists": paged("mockplaylist", simplified_playlist) })
+        }
+        ("GET", ["recommendations"]) => recommendations(),
+        ("GET", ["recommendations", "available-genre-seeds"]) => genre_seeds(),
+        // Kosovo isn't in `Country`
+        ("GET", ["markets"]) => json!({ "markets": ["ES", "US", "XK"] }),
+        // Spotify answers without any data when it has no features for the
+        // tracks, like for the ones named `mocknofeatures` here
+        ("GET", ["audio-features"]) if ids.iter().all(|id| id.starts_with("mocknofeatures")) => {
+            return MockResponse::empty(200)
+        }
+        ("GET", ["audio-features"]) => json!({ "audio_features": many(audio_features) }),
+        ("GET", ["audio-features", id]) => audio_features(id),
+        ("GET", ["audio-analysis", _]) => audio_analysis(),
+
+        // Player
+        ("GET", ["me", "player"]) => current_playback(),
+        ("GET", ["me", "player", "currently-playing"]) => currently_playing(),
+        ("GET", ["me", "player", "devices"]) => json!({ "devices": [device()] }),
+        ("GET", ["me", "player", "queue"]) => queue(),
+        ("GET", ["me", "player", "recently-played"]) => {
+            cursor_page(request, "before", "mocktrack", play_history)
+        }
+        ("PUT", ["me", "player"])
+        | ("PUT", ["me", "player", "play"])
+        | ("PUT", ["me", "player", "pause"])
+        | ("PUT", ["me", "player", "seek"])
+        | ("PUT", ["me", "player", "repeat"])
+        | ("PUT", ["me", "player", "volume"])
+        | ("PUT", ["me", "player", "shuffle"])
+        | ("POST", ["me", "player", "next"])
+        | ("POST", ["me", "player", "previous"])
+        | ("POST", ["me", "player", "queue"]) => return MockResponse::empty(204),
+
+        // Shows and episodes
+        ("GET", ["shows"]) => json!({ "shows": many(simplified_show) }),
+        ("GET", ["shows", id]) => full_show(id),
+        ("GET", ["shows", _, "episodes"]) => paged("mockepisode", simplified_episode),
+        ("GET", ["episodes"]) => json!({ "episodes": many(full_episode) }),
+        ("GET", ["episodes", id]) => full_episode(id),
+
+        _ => return MockResponse::error(404, "Service not found"),
+    };
+
+    MockResponse::ok(response)
+}
+
+/// Search results of the first type requested.
+fn search(request: &MockRequest) -> Value {
+    use responses::*;
+
+    let _type = request
+        .param("type")
+        .and_then(|types| types.split(',').next())
+        .unwrap_or("track");
+    match _type {
+        "artist" => json!({ "artists": page(request, "mockartist", full_artist) }),
+        "album" => json!({ "albums": page(request, "mockalbum", simplified_album) }),
+        "playlist" => json!({ "playlists": page(request, "mockplaylist", simplified_playlist) }),
+        "show" => json!({ "shows": page(request, "mockshow", simplified_show) }),
+        "episode" => json!({ "episodes": page(request, "mockepisode", simplified_episode) }),
+        _ => json!({ "tracks": page(request, "mocktrack", full_track) }),
+    }
+}
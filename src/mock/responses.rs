@@ -0,0 +1,554 @@
+//! Canned responses of the mock server. They are built from the IDs in the
+//! request when possible, so that the returned objects match what was asked
+//! for.
+
+use serde_json::{json, Value};
+
+use super::MockRequest;
+
+/// The number of items in every paginated response.
+pub const MOCK_PAGE_TOTAL: u32 = 5;
+
+fn external_urls(_type: &str, id: &str) -> Value {
+    json!({ "spotify": format!("https://open.spotify.com/{}/{}", _type, id) })
+}
+
+fn href(_type: &str, id: &str) -> String {
+    format!("https://api.spotify.com/v1/{}s/{}", _type, id)
+}
+
+fn uri(_type: &str, id: &str) -> String {
+    format!("spotify:{}:{}", _type, id)
+}
+
+pub fn images() -> Value {
+    json!([{
+        "height": 640,
+        "url": "https://i.scdn.co/image/ab67616d0000b273mock",
+        "width": 640
+    }])
+}
+
+fn copyrights() -> Value {
+    json!([{ "text": "(C) Mock Records", "type": "C" }])
+}
+
+pub fn simplified_artist(id: &str) -> Value {
+    json!({
+        "external_urls": external_urls("artist", id),
+        "href": href("artist", id),
+        "id": id,
+        "name": "Mock Artist",
+        "type": "artist",
+        "uri": uri("artist", id)
+    })
+}
+
+pub fn full_artist(id: &str) -> Value {
+    json!({
+        "external_urls": external_urls("artist", id),
+        "followers": { "href": null, "total": 1000 },
+        "genres": ["mock rock"],
+        "href": href("artist", id),
+        "id": id,
+        "images": images(),
+        "name": "Mock Artist",
+        "popularity": 50,
+        "type": "artist",
+        "uri": uri("artist", id)
+    })
+}
+
+pub fn simplified_album(id: &str) -> Value {
+    json!({
+        "album_group": "album",
+        "album_type": "album",
+        "artists": [simplified_artist("mockartist")],
+        "available_markets": ["US"],
+        "external_urls": external_urls("album", id),
+        "href": href("album", id),
+        "id": id,
+        "images": images(),
+        "name": "Mock Album",
+        "release_date": "2020-01-01",
+        "release_date_precision": "day",
+        "type": "album",
+        "uri": uri("album", id)
+    })
+}
+
+pub fn full_album(id: &str) -> Value {
+    let tracks = (0..MOCK_PAGE_TOTAL)
+        .map(|i| simplified_track(&format!("mocktrack{}", i)))
+        .collect();
+    json!({
+        "artists": [simplified_artist("mockartist")],
+        "album_type": "album",
+        "available_markets": ["US"],
+        "copyrights": copyrights(),
+        "external_ids": { "upc": "000000000000" },
+        "external_urls": external_urls("album", id),
+        "genres": [],
+        "href": href("album", id),
+        "id": id,
+        "images": images(),
+        "name": "Mock Album",
+        "popularity": 50,
+        "release_date": "2020-01-01",
+        "release_date_precision": "day",
+        "tracks": page_of(&format!("{}/tracks", href("album", id)), tracks, 50, 0),
+        "type": "album",
+        "uri": uri("album", id)
+    })
+}
+
+pub fn simplified_track(id: &str) -> Value {
+    json!({
+        "artists": [simplified_artist("mockartist")],
+        "available_markets": ["US"],
+        "disc_number": 1,
+        "duration_ms": 180000,
+        "explicit": false,
+        "external_urls": external_urls("track", id),
+        "href": href("track", id),
+        "id": id,
+        "is_local": false,
+        "is_playable": true,
+        "linked_from": null,
+        "restrictions": null,
+        "name": "Mock Track",
+        "preview_url": null,
+        "track_number": 1,
+        "type": "track",
+        "uri": uri("track", id)
+    })
+}
+
+pub fn full_track(id: &str) -> Value {
+    json!({
+        "album": simplified_album("mockalbum"),
+        "artists": [simplified_artist("mockartist")],
+        "available_markets": ["US"],
+        "disc_number": 1,
+        "duration_ms": 180000,
+        "explicit": false,
+        "external_ids": { "isrc": "USMOCK0000000" },
+        "external_urls": external_urls("track", id),
+        "href": href("track", id),
+        "id": id,
+        "is_local": false,
+        "name": "Mock Track",
+        "popularity": 50,
+        "preview_url": null,
+        "track_number": 1,
+        "type": "track",
+        "uri": uri("track", id)
+    })
+}
+
+pub fn public_user(id: &str) -> Value {
+    json!({
+        "display_name": "Mock User",
+        "external_urls": external_urls("user", id),
+        "followers": { "href": null, "total": 10 },
+        "href": href("user", id),
+        "id": id,
+        "images": [],
+        "type": "user",
+        "uri": uri("user", id)
+    })
+}
+
+pub fn private_user() -> Value {
+    let id = "mockuser";
+    json!({
+        "country": "US",
+        "display_name": "Mock User",
+        "email": "mock@example.com",
+        "explicit_content": { "filter_enabled": false, "filter_locked": false },
+        "external_urls": external_urls("user", id),
+        "followers": { "href": null, "total": 10 },
+        "href": href("user", id),
+        "id": id,
+        "images": [],
+        "product": "premium",
+        "type": "user",
+        "uri": uri("user", id)
+    })
+}
+
+pub fn simplified_playlist(id: &str) -> Value {
+    json!({
+        "collaborative": false,
+        "external_urls": external_urls("playlist", id),
+        "href": href("playlist", id),
+        "id": id,
+        "images": images(),
+        "name": "Mock Playlist",
+        "owner": public_user("mockuser"),
+        "public": true,
+        "snapshot_id": "mocksnapshot",
+        "tracks": { "href": format!("{}/tracks", href("playlist", id)), "total": MOCK_PAGE_TOTAL },
+        "type": "playlist",
+        "uri": uri("playlist", id)
+    })
+}
+
+pub fn full_playlist(id: &str) -> Value {
+    let items = (0..MOCK_PAGE_TOTAL)
+        .map(|i| playlist_item(&format!("mocktrack{}", i)))
+        .collect();
+    json!({
+        "collaborative": false,
+        "description": "A mock playlist",
+        "external_urls": external_urls("playlist", id),
+        "followers": { "href": null, "total": 10 },
+        "href": href("playlist", id),
+        "id": id,
+        "images": images(),
+        "name": "Mock Playlist",
+        "owner": public_user("mockuser"),
+        "public": true,
+        "snapshot_id": "mocksnapshot",
+        "tracks": page_of(&format!("{}/tracks", href("playlist", id)), items, 100, 0),
+        "type": "playlist",
+        "uri": uri("playlist", id)
+    })
+}
+
+pub fn playlist_item(id: &str) -> Value {
+    json!({
+        "added_at": "2020-01-01T00:00:00Z",
+        "added_by": public_user("mockuser"),
+        "is_local": false,
+        "track": full_track(id)
+    })
+}
+
+pub fn playlist_result() -> Value {
+    json!({ "snapshot_id": "mocksnapshot" })
+}
+
+pub fn saved_album(id: &str) -> Value {
+    json!({ "added_at": "2020-01-01T00:00:00Z", "album": full_album(id) })
+}
+
+pub fn saved_track(id: &str) -> Value {
+    json!({ "added_at": "2020-01-01T00:00:00Z", "track": full_track(id) })
+}
+
+pub fn play_history(id: &str) -> Value {
+    json!({
+        "track": full_track(id),
+        "played_at": "2020-01-01T00:00:00Z",
+        "context": context()
+    })
+}
+
+pub fn category(id: &str) -> Value {
+    json!({
+        "href": format!("https://api.spotify.com/v1/browse/categories/{}", id),
+        "icons": images(),
+        "id": id,
+        "name": "Mock Category"
+    })
+}
+
+pub fn recommendations() -> Value {
+    let tracks: Vec<Value> = (0..MOCK_PAGE_TOTAL)
+        .map(|i| simplified_track(&format!("mocktrack{}", i)))
+        .collect();
+    json!({
+        "seeds": [{
+            "afterFilteringSize": 250,
+            "afterRelinkingSize": 250,
+            "href": href("artist", "mockartist"),
+            "id": "mockartist",
+            "initialPoolSize": 250,
+            "type": "artist"
+        }],
+        "tracks": tracks
+    })
+}
+
+pub fn genre_seeds() -> Value {
+    json!({ "genres": ["acoustic", "jazz", "rock"] })
+}
+
+pub fn audio_features(id: &str) -> Value {
+    json!({
+        "acousticness": 0.5,
+        "analysis_url": format!("https://api.spotify.com/v1/audio-analysis/{}", id),
+        "danceability": 0.5,
+        "duration_ms": 180000,
+        "energy": 0.5,
+        "id": id,
+        "instrumentalness": 0.5,
+        "key": 5,
+        "liveness": 0.5,
+        "loudness": -5.0,
+        "mode": 1,
+        "speechiness": 0.05,
+        "tempo": 120.0,
+        "time_signature": 4,
+        "track_href": href("track", id),
+        "type": "audio_features",
+        "uri": uri("track", id),
+        "valence": 0.5
+    })
+}
+
+pub fn audio_analysis() -> Value {
+    let interval = json!({ "start": 0.0, "duration": 0.5, "confidence": 0.9 });
+    json!({
+        "bars": [interval],
+        "beats": [interval],
+        "meta": {
+            "analyzer_version": "4.0.0",
+            "platform": "Linux",
+            "detailed_status": "OK",
+            "status_code": 0,
+            "timestamp": 1577836800,
+            "analysis_time": 1.0,
+            "input_process": "libvorbisfile L+R 44100->22050"
+        },
+        "sections": [{
+            "start": 0.0,
+            "duration": 180.0,
+            "confidence": 1.0,
+            "loudness": -5.0,
+            "tempo": 120.0,
+            "tempo_confidence": 0.9,
+            "key": 5,
+            "key_confidence": 0.9,
+            "mode": 1,
+            "mode_confidence": 0.9,
+            "time_signature": 4,
+            "time_signature_confidence": 1.0
+        }],
+        "segments": [{
+            "start": 0.0,
+            "duration": 0.5,
+            "confidence": 0.9,
+            "loudness_start": -60.0,
+            "loudness_max_time": 0.1,
+            "loudness_max": -5.0,
+            "loudness_end": -10.0,
+            "pitches": vec![0.5; 12],
+            "timbre": vec![0.5; 12]
+        }],
+        "tatums": [interval],
+        "track": {
+            "num_samples": 3969000,
+            "duration": 180.0,
+            "sample_md5": "",
+            "offset_seconds": 0,
+            "window_seconds": 0,
+            "analysis_sample_rate": 22050,
+            "analysis_channels": 1,
+            "end_of_fade_in": 0.0,
+            "start_of_fade_out": 175.0,
+            "loudness": -5.0,
+            "tempo": 120.0,
+            "tempo_confidence": 0.9,
+            "time_signature": 4,
+            "time_signature_confidence": 1.0,
+            "key": 5,
+            "key_confidence": 0.9,
+            "mode": 1,
+            "mode_confidence": 0.9,
+            "codestring": "",
+            "code_version": 3.15,
+            "echoprintstring": "",
+            "echoprint_version": 4.12,
+            "synchstring": "",
+            "synch_version": 1.0,
+            "rhythmstring": "",
+            "rhythm_version": 1.0
+        }
+    })
+}
+
+pub fn device() -> Value {
+    json!({
+        "id": "mockdevice",
+        "is_active": true,
+        "is_private_session": false,
+        "is_restricted": false,
+        "name": "Mock Device",
+        "type": "Computer",
+        "volume_percent": 50
+    })
+}
+
+fn context() -> Value {
+    let id = "mockplaylist";
+    json!({
+        "uri": uri("playlist", id),
+        "href": href("playlist", id),
+        "external_urls": external_urls("playlist", id),
+        "type": "playlist"
+    })
+}
+
+pub fn currently_playing() -> Value {
+    json!({
+        "context": context(),
+        "timestamp": 1577836800000u64,
+        "progress_ms": 60000,
+        "is_playing": true,
+        "item": full_track("mocktrack0"),
+        "currently_playing_type": "track",
+        "actions": { "disallows": { "resuming": true } }
+    })
+}
+
+pub fn queue() -> Value {
+    json!({
+        "currently_playing": full_track("mocktrack0"),
+        "queue": [full_track("mocktrack1"), full_episode("mockepisode0")]
+    })
+}
+
+pub fn current_playback() -> Value {
+    let mut playback = currently_playing();
+    playback["device"] = device();
+    playback["repeat_state"] = json!("off");
+    playback["shuffle_state"] = json!(false);
+    playback
+}
+
+pub fn simplified_show(id: &str) -> Value {
+    json!({
+        "available_markets": ["US"],
+        "copyrights": copyrights(),
+        "description": "A mock show",
+        "explicit": false,
+        "external_urls": external_urls("show", id),
+        "href": href("show", id),
+        "id": id,
+        "images": images(),
+        "is_externally_hosted": false,
+        "languages": ["en"],
+        "media_type": "audio",
+        "name": "Mock Show",
+        "publisher": "Mock Publisher",
+        "type": "show",
+        "uri": uri("show", id)
+    })
+}
+
+pub fn full_show(id: &str) -> Value {
+    let episodes = (0..MOCK_PAGE_TOTAL)
+        .map(|i| simplified_episode(&format!("mockepisode{}", i)))
+        .collect();
+    let mut show = simplified_show(id);
+    show["episodes"] = page_of(&format!("{}/episodes", href("show", id)), episodes, 50, 0);
+    show
+}
+
+pub fn saved_show(id: &str) -> Value {
+    json!({ "added_at": "2020-01-01T00:00:00Z", "show": simplified_show(id) })
+}
+
+pub fn simplified_episode(id: &str) -> Value {
+    json!({
+        "audio_preview_url": null,
+        "description": "A mock episode",
+        "duration_ms": 1800000,
+        "explicit": false,
+        "external_urls": external_urls("episode", id),
+        "href": href("episode", id),
+        "id": id,
+        "images": images(),
+        "is_externally_hosted": false,
+        "is_playable": true,
+        "language": "en",
+        "languages": ["en"],
+        "name": "Mock Episode",
+        "release_date": "2020-01-01",
+        "release_date_precision": "day",
+        "resume_point": { "fully_played": false, "resume_position_ms": 0 },
+        "type": "episode",
+        "uri": uri("episode", id)
+    })
+}
+
+pub fn full_episode(id: &str) -> Value {
+    let mut episode = simplified_episode(id);
+    episode["show"] = simplified_show("mockshow");
+    episode
+}
+
+/// A page with the given items, which have already been sliced.
+fn page_of(href: &str, items: Vec<Value>, limit: u32, offset: u32) -> Value {
+    let next = offset + limit;
+    json!({
+        "href": href,
+        "items": items,
+        "limit": limit,
+        "next": if next < MOCK_PAGE_TOTAL {
+            Some(format!("{}?offset={}&limit={}", href, next, limit))
+        } else {
+            None
+        },
+        "offset": offset,
+        "previous": null,
+        "total": MOCK_PAGE_TOTAL
+    })
+}
+
+fn request_href(request: &MockRequest) -> String {
+    format!("https://api.spotify.com{}", request.path)
+}
+
+fn param_or(request: &MockRequest, key: &str, default: u32) -> u32 {
+    request
+        .param(key)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+/// An offset-based page of `MOCK_PAGE_TOTAL` items in total, honoring the
+/// `limit` and `offset` parameters of the request. `item` is given the
+/// generated ID of each item.
+pub fn page(request: &MockRequest, prefix: &str, item: fn(&str) -> Value) -> Value {
+    let limit = param_or(request, "limit", 20);
+    let offset = param_or(request, "offset", 0);
+    let items = (offset..MOCK_PAGE_TOTAL.min(offset + limit))
+        .map(|i| item(&format!("{}{}", prefix, i)))
+        .collect();
+    page_of(&request_href(request), items, limit, offset)
+}
+
+/// A cursor-based page of `MOCK_PAGE_TOTAL` items in total, honoring the
+/// `limit` and `after` parameters of the request. The cursors are the item
+/// indices.
+/// A page of a cursor-based endpoint, which follows the `after` or the
+/// `before` cursor. The cursors are simply the index of the next item.
+pub fn cursor_page(
+    request: &MockRequest,
+    cursor: &str,
+    prefix: &str,
+    item: fn(&str) -> Value,
+) -> Value {
+    let limit = param_or(request, "limit", 20);
+    let start = param_or(request, cursor, 0);
+    let end = MOCK_PAGE_TOTAL.min(start + limit);
+    let items: Vec<Value> = (start..end)
+        .map(|i| item(&format!("{}{}", prefix, i)))
+        .collect();
+    let more = end < MOCK_PAGE_TOTAL;
+    json!({
+        "href": request_href(request),
+        "items": items,
+        "limit": limit,
+        "next": if more {
+            Some(format!("{}?{}={}&limit={}", request_href(request), cursor, end, limit))
+        } else {
+            None
+        },
+        "cursors": { cursor: if more { Some(end.to_string()) } else { None } },
+        "total": MOCK_PAGE_TOTAL
+    })
+}
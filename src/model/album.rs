@@ -67,7 +67,7 @@ pub struct FullAlbum {
 ///
 /// [Reference](https://developer.spotify.com/documentation/web-api/reference/albums/get-several-albums/)
 #[derive(Deserialize)]
-pub(in crate) struct FullAlbums {
+pub(crate) struct FullAlbums {
     pub albums: Vec<FullAlbum>,
 }
 
@@ -75,7 +75,7 @@ pub(in crate) struct FullAlbums {
 ///
 /// [Reference](https://developer.spotify.com/web-api/get-list-new-releases/)
 #[derive(Deserialize)]
-pub(in crate) struct PageSimpliedAlbums {
+pub(crate) struct PageSimpliedAlbums {
     pub albums: Page<SimplifiedAlbum>,
 }
 
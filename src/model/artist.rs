@@ -41,7 +41,7 @@ pub struct FullArtist {
 ///
 /// [Reference](https://developer.spotify.com/documentation/web-api/reference/artists/get-several-artists/)
 #[derive(Deserialize)]
-pub(in crate) struct FullArtists {
+pub(crate) struct FullArtists {
     pub artists: Vec<FullArtist>,
 }
 
@@ -49,6 +49,6 @@ pub(in crate) struct FullArtists {
 ///
 /// [Reference](https://developer.spotify.com/documentation/web-api/reference/follow/get-followed/)
 #[derive(Deserialize)]
-pub(in crate) struct CursorPageFullArtists {
+pub(crate) struct CursorPageFullArtists {
     pub artists: CursorBasedPage<FullArtist>,
 }
@@ -38,7 +38,7 @@ pub struct AudioFeatures {
 ///
 /// [Reference](https://developer.spotify.com/documentation/web-api/reference/tracks/get-several-audio-features/)
 #[derive(Deserialize)]
-pub(in crate) struct AudioFeaturesPayload {
+pub(crate) struct AudioFeaturesPayload {
     pub audio_features: Vec<AudioFeatures>,
 }
 
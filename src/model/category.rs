@@ -17,6 +17,6 @@ pub struct Category {
 ///
 /// [Reference](https://developer.spotify.com/web-api/get-list-categories/)
 #[derive(Deserialize)]
-pub(in crate) struct PageCategory {
+pub(crate) struct PageCategory {
     pub categories: Page<Category>,
 }
@@ -20,7 +20,7 @@ pub struct Device {
 ///
 /// [Reference](https://developer.spotify.com/documentation/web-api/reference/player/get-a-users-available-devices/)
 #[derive(Deserialize)]
-pub(in crate) struct DevicePayload {
+pub(crate) struct DevicePayload {
     pub devices: Vec<Device>,
 }
 
@@ -1,10 +1,10 @@
 use serde::{Deserialize, Serialize};
-use strum::ToString;
+use strum::{EnumString, ToString};
 
 /// ISO 3166-1 alpha-2 country code, from [country-list](https://datahub.io/core/country-list)
 ///
 /// [Reference](https://en.wikipedia.org/wiki/ISO_3166-1_alpha-2)
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug, ToString)]
+#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug, ToString, EnumString)]
 pub enum Country {
     #[strum(serialize = "AF")]
     #[serde(rename = "AF")]
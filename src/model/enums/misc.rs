@@ -1,5 +1,7 @@
-use serde::{Deserialize, Serialize};
-use strum::ToString;
+use serde::{Deserialize, Serialize, Serializer};
+use strum::{AsRefStr, EnumString, ToString};
+
+use std::fmt;
 
 /// Disallows object:
 /// `interrupting_playback`, `pausing`, `resuming`, `seeking`, `skipping_next`,
@@ -79,3 +81,55 @@ pub enum RestrictionReason {
     Product,
     Explict,
 }
+
+/// The reason of a player error, like `NO_ACTIVE_DEVICE` or
+/// `PREMIUM_REQUIRED`. The ones that aren't known, including Spotify's own
+/// `UNKNOWN`, are kept as they are in `Unknown`.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/object-model/#player-error-reasons)
+#[derive(Clone, Deserialize, PartialEq, Eq, Debug, Hash, AsRefStr, EnumString)]
+#[serde(from = "String")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum PlayerErrorReason {
+    NoPrevTrack,
+    NoNextTrack,
+    NoSpecificTrack,
+    AlreadyPaused,
+    NotPaused,
+    NotPlayingLocally,
+    NotPlayingTrack,
+    NotPlayingContext,
+    EndlessContext,
+    ContextDisallow,
+    AlreadyPlaying,
+    RateLimited,
+    RemoteControlDisallow,
+    DeviceNotControllable,
+    VolumeControlDisallow,
+    NoActiveDevice,
+    PremiumRequired,
+    #[strum(default)]
+    Unknown(String),
+}
+
+impl From<String> for PlayerErrorReason {
+    fn from(reason: String) -> Self {
+        // Parsing can't fail thanks to the `Unknown` variant
+        reason.parse().unwrap()
+    }
+}
+
+impl fmt::Display for PlayerErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayerErrorReason::Unknown(reason) => f.write_str(reason),
+            reason => f.write_str(reason.as_ref()),
+        }
+    }
+}
+
+impl Serialize for PlayerErrorReason {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
@@ -0,0 +1,255 @@
+//! Strongly typed Spotify IDs, which can be parsed from a bare ID, a Spotify
+//! URI or an `open.spotify.com` URL.
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use super::enums::Type;
+
+const URL_PREFIXES: [&str; 2] = ["https://open.spotify.com/", "http://open.spotify.com/"];
+
+/// Spotify ID or URI parsing error
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum IdError {
+    /// The URI doesn't start with `spotify:`, and the URL isn't from
+    /// `open.spotify.com`.
+    #[error("invalid prefix")]
+    InvalidPrefix,
+    /// The URI or URL can't be split into its type and ID parts.
+    #[error("invalid format")]
+    InvalidFormat,
+    /// The URI or URL has a type different from the expected one.
+    #[error("invalid type")]
+    InvalidType,
+    /// The ID is empty or contains invalid characters.
+    #[error("invalid id")]
+    InvalidId,
+}
+
+/// Common behavior of all the Spotify ID types.
+pub trait Id: fmt::Debug {
+    /// The bare Spotify ID, like `4iV5W9uYEdYUVa79Axb7Rh`.
+    fn id(&self) -> &str;
+
+    /// The type of the object identified.
+    fn _type(&self) -> Type;
+
+    /// The Spotify URI, like `spotify:track:4iV5W9uYEdYUVa79Axb7Rh`.
+    fn uri(&self) -> String {
+        format!("spotify:{}:{}", self._type().to_string(), self.id())
+    }
+
+    /// The Spotify URL, like
+    /// `https://open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh`.
+    fn url(&self) -> String {
+        format!(
+            "{}{}/{}",
+            URL_PREFIXES[0],
+            self._type().to_string(),
+            self.id()
+        )
+    }
+}
+
+/// IDs of items that can be played or added to the queue: tracks and episodes.
+pub trait PlayableId: Id {}
+
+/// IDs of the contexts playback can be started from: artists, albums,
+/// playlists and shows.
+pub trait PlayContextId: Id {}
+
+/// Splits a URI like `spotify:track:ID` or a URL like
+/// `https://open.spotify.com/track/ID?si=...` into its type and ID. Legacy
+/// URIs like `spotify:user:USER:playlist:ID` are supported as well.
+fn split_uri(uri: &str) -> Result<(&str, &str), IdError> {
+    let (rest, sep) = if let Some(rest) = uri.strip_prefix("spotify:") {
+        (rest, ':')
+    } else if let Some(rest) = URL_PREFIXES.iter().find_map(|p| uri.strip_prefix(p)) {
+        // Query parameters like `?si=...` are often added when sharing
+        let end = rest.find(&['?', '#'][..]).unwrap_or(rest.len());
+        (&rest[..end], '/')
+    } else {
+        return Err(IdError::InvalidPrefix);
+    };
+
+    let mut parts = rest.rsplitn(3, sep);
+    match (parts.next(), parts.next()) {
+        (Some(id), Some(_type)) => Ok((_type, id)),
+        _ => Err(IdError::InvalidFormat),
+    }
+}
+
+/// Most IDs are base-62 strings, while user IDs are usernames with fewer
+/// restrictions.
+fn is_valid_id(_type: Type, id: &str) -> bool {
+    match _type {
+        Type::User => !id.is_empty() && !id.contains(&[':', '/'][..]),
+        _ => !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric()),
+    }
+}
+
+macro_rules! define_idtypes {
+    ($($(#[$attr:meta])* $name:ident => $type:ident),+ $(,)?) => {
+        $(
+            $(#[$attr])*
+            #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+            #[serde(try_from = "String", into = "String")]
+            pub struct $name(String);
+
+            impl $name {
+                /// Parses a bare Spotify ID, checking that its format is
+                /// valid.
+                pub fn from_id(id: &str) -> Result<Self, IdError> {
+                    if is_valid_id(Type::$type, id) {
+                        Ok($name(id.to_owned()))
+                    } else {
+                        Err(IdError::InvalidId)
+                    }
+                }
+
+                /// Parses a Spotify URI or an `open.spotify.com` URL,
+                /// checking that its type matches.
+                pub fn from_uri(uri: &str) -> Result<Self, IdError> {
+                    let (_type, id) = split_uri(uri)?;
+                    if _type != Type::$type.to_string() {
+                        return Err(IdError::InvalidType);
+                    }
+
+                    Self::from_id(id)
+                }
+
+                /// Parses either a Spotify URI or URL, or a bare ID if it
+                /// doesn't have a valid prefix.
+                pub fn from_id_or_uri(id_or_uri: &str) -> Result<Self, IdError> {
+                    match Self::from_uri(id_or_uri) {
+                        Err(IdError::InvalidPrefix) => Self::from_id(id_or_uri),
+                        result => result,
+                    }
+                }
+            }
+
+            impl Id for $name {
+                fn id(&self) -> &str {
+                    &self.0
+                }
+
+                fn _type(&self) -> Type {
+                    Type::$type
+                }
+            }
+
+            /// Deserializes the bare ID, checking that its format is valid.
+            impl TryFrom<String> for $name {
+                type Error = IdError;
+
+                fn try_from(id: String) -> Result<Self, Self::Error> {
+                    Self::from_id(&id)
+                }
+            }
+
+            impl From<$name> for String {
+                fn from(id: $name) -> Self {
+                    id.0
+                }
+            }
+
+            impl FromStr for $name {
+                type Err = IdError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    Self::from_id_or_uri(s)
+                }
+            }
+
+            /// Displays the Spotify URI.
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "{}", self.uri())
+                }
+            }
+        )+
+    };
+}
+
+define_idtypes!(
+    /// ID of an artist
+    ArtistId => Artist,
+    /// ID of an album
+    AlbumId => Album,
+    /// ID of a track
+    TrackId => Track,
+    /// ID of a playlist
+    PlaylistId => Playlist,
+    /// ID of a user, which is its username
+    UserId => User,
+    /// ID of a show (podcast)
+    ShowId => Show,
+    /// ID of an episode of a show
+    EpisodeId => Episode,
+);
+
+impl PlayableId for TrackId {}
+impl PlayableId for EpisodeId {}
+
+impl PlayContextId for ArtistId {}
+impl PlayContextId for AlbumId {}
+impl PlayContextId for PlaylistId {}
+impl PlayContextId for ShowId {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_uri() {
+        let id = ArtistId::from_uri("spotify:artist:2WX2uTcsvV5OnS0inACecP").unwrap();
+        assert_eq!(id.id(), "2WX2uTcsvV5OnS0inACecP");
+
+        let id =
+            PlaylistId::from_uri("spotify:user:spotify:playlist:59ZbFPES4DQwEjBpWHzrtC").unwrap();
+        assert_eq!(id.id(), "59ZbFPES4DQwEjBpWHzrtC");
+
+        let id = AlbumId::from_uri("https://open.spotify.com/album/0sNOF9WDwhWunNAHPD3Baj?si=abc")
+            .unwrap();
+        assert_eq!(id.id(), "0sNOF9WDwhWunNAHPD3Baj");
+
+        assert_eq!(
+            ArtistId::from_uri("spotify:album:2WX2uTcsvV5OnS0inACecP"),
+            Err(IdError::InvalidType)
+        );
+        assert_eq!(
+            ArtistId::from_uri("spotify-artist-2WX2uTcsvV5OnS0inACecP"),
+            Err(IdError::InvalidPrefix)
+        );
+        assert_eq!(
+            ArtistId::from_uri("spotify:2WX2uTcsvV5OnS0inACecP"),
+            Err(IdError::InvalidFormat)
+        );
+        assert_eq!(
+            ArtistId::from_uri("spotify:artist:"),
+            Err(IdError::InvalidId)
+        );
+    }
+
+    #[test]
+    fn test_from_id_or_uri() {
+        let id = TrackId::from_id_or_uri("1301WleyT98MSxVHPZCA6M").unwrap();
+        assert_eq!(id.uri(), "spotify:track:1301WleyT98MSxVHPZCA6M");
+
+        let id: TrackId = "spotify:track:4iV5W9uYEdYUVa79Axb7Rh".parse().unwrap();
+        assert_eq!(id.id(), "4iV5W9uYEdYUVa79Axb7Rh");
+        assert_eq!(
+            id.url(),
+            "https://open.spotify.com/track/4iV5W9uYEdYUVa79Axb7Rh"
+        );
+
+        assert_eq!(
+            TrackId::from_id_or_uri("spotify-track-4iV5W9uYEdYUVa79Axb7Rh"),
+            Err(IdError::InvalidId)
+        );
+        assert!(UserId::from_id("wizzler.test_user").is_ok());
+    }
+}
@@ -0,0 +1,117 @@
+//! The locale of the browse endpoints, made of a language and a country.
+use thiserror::Error;
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::enums::Country;
+
+/// The ISO 639-1 language codes, sorted.
+const LANGUAGES: &[&str] = &[
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg", "bh",
+    "bi", "bm", "bn", "bo", "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy", "da",
+    "de", "dv", "dz", "ee", "el", "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr",
+    "fy", "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr", "ht", "hu", "hy", "hz",
+    "ia", "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv", "ka", "kg", "ki", "kj",
+    "kk", "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln",
+    "lo", "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms", "mt", "my", "na", "nb",
+    "nd", "ne", "ng", "nl", "nn", "no", "nr", "nv", "ny", "oc", "oj", "om", "or", "os", "pa", "pi",
+    "pl", "ps", "pt", "qu", "rm", "rn", "ro", "ru", "rw", "sa", "sc", "sd", "se", "sg", "si", "sk",
+    "sl", "sm", "sn", "so", "sq", "sr", "ss", "st", "su", "sv", "sw", "ta", "te", "tg", "th", "ti",
+    "tk", "tl", "tn", "to", "tr", "ts", "tt", "tw", "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo",
+    "wa", "wo", "xh", "yi", "yo", "za", "zh", "zu",
+];
+
+/// Locale parsing error
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum LocaleError {
+    /// The locale isn't a language and a country joined by an underscore.
+    #[error("invalid format")]
+    InvalidFormat,
+    /// The language isn't a lowercase ISO 639-1 code.
+    #[error("invalid language")]
+    InvalidLanguage,
+    /// The country isn't an uppercase ISO 3166-1 alpha-2 code in `Country`.
+    #[error("invalid country")]
+    InvalidCountry,
+}
+
+/// The desired language of the browse endpoints, consisting of an ISO 639-1
+/// language code and a country, like `es_MX` for Spanish in Mexico.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/browse/get-list-featured-playlists/)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Locale {
+    language: &'static str,
+    country: Country,
+}
+
+impl Locale {
+    /// Builds a locale, checking that the language is a lowercase ISO 639-1
+    /// code like `es`.
+    pub fn new(language: &str, country: Country) -> Result<Self, LocaleError> {
+        let language = LANGUAGES
+            .binary_search(&language)
+            .map(|pos| LANGUAGES[pos])
+            .map_err(|_| LocaleError::InvalidLanguage)?;
+
+        Ok(Locale { language, country })
+    }
+
+    pub fn language(&self) -> &str {
+        self.language
+    }
+
+    pub fn country(&self) -> Country {
+        self.country
+    }
+}
+
+impl FromStr for Locale {
+    type Err = LocaleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '_');
+        match (parts.next(), parts.next()) {
+            (Some(language), Some(country)) => {
+                let country = country.parse().map_err(|_| LocaleError::InvalidCountry)?;
+                Self::new(language, country)
+            }
+            _ => Err(LocaleError::InvalidFormat),
+        }
+    }
+}
+
+/// Displays the locale as expected by Spotify, like `es_MX`.
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.language, self.country.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale() {
+        let locale = Locale::new("es", Country::Mexico).unwrap();
+        assert_eq!(locale.to_string(), "es_MX");
+        assert_eq!("es_MX".parse(), Ok(locale));
+        assert_eq!(locale.language(), "es");
+        assert_eq!(locale.country(), Country::Mexico);
+    }
+
+    #[test]
+    fn test_invalid_locale() {
+        assert_eq!("es".parse::<Locale>(), Err(LocaleError::InvalidFormat));
+        assert_eq!("ES_MX".parse::<Locale>(), Err(LocaleError::InvalidLanguage));
+        assert_eq!("xx_MX".parse::<Locale>(), Err(LocaleError::InvalidLanguage));
+        assert_eq!("es_mx".parse::<Locale>(), Err(LocaleError::InvalidCountry));
+        assert_eq!("es_XK".parse::<Locale>(), Err(LocaleError::InvalidCountry));
+        assert_eq!(
+            Locale::new("spanish", Country::Spain),
+            Err(LocaleError::InvalidLanguage)
+        );
+    }
+}
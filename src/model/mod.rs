@@ -6,7 +6,9 @@ pub mod category;
 pub mod context;
 pub mod device;
 pub mod enums;
+pub mod idtypes;
 pub mod image;
+pub mod locale;
 pub mod offset;
 pub mod page;
 pub mod playing;
@@ -37,7 +39,7 @@ impl<'de> de::Visitor<'de> for DurationVisitor {
 }
 
 /// Deserialize `std::time::Duration` from milliseconds (represented as u64)
-pub(in crate) fn from_duration_ms<'de, D>(d: D) -> Result<Duration, D::Error>
+pub(crate) fn from_duration_ms<'de, D>(d: D) -> Result<Duration, D::Error>
 where
     D: de::Deserializer<'de>,
 {
@@ -45,7 +47,7 @@ where
 }
 
 /// Serialize `std::time::Duration` to milliseconds (represented as u64)
-pub(in crate) fn to_duration_ms<S>(x: &Duration, s: S) -> Result<S::Ok, S::Error>
+pub(crate) fn to_duration_ms<S>(x: &Duration, s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -79,7 +81,7 @@ impl<'de> de::Visitor<'de> for DateTimeVisitor {
 }
 
 /// Deserialize Unix millisecond timestamp to `DateTime<Utc>`
-pub(in crate) fn from_millisecond_timestamp<'de, D>(d: D) -> Result<DateTime<Utc>, D::Error>
+pub(crate) fn from_millisecond_timestamp<'de, D>(d: D) -> Result<DateTime<Utc>, D::Error>
 where
     D: de::Deserializer<'de>,
 {
@@ -87,7 +89,7 @@ where
 }
 
 /// Serialize DateTime<Utc> to Unix millisecond timestamp
-pub(in crate) fn to_millisecond_timestamp<S>(x: &DateTime<Utc>, s: S) -> Result<S::Ok, S::Error>
+pub(crate) fn to_millisecond_timestamp<S>(x: &DateTime<Utc>, s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -121,7 +123,7 @@ impl<'de> de::Visitor<'de> for OptionDurationVisitor {
 }
 
 /// Deserialize `Option<std::time::Duration>` from milliseconds (represented as u64)
-pub(in crate) fn from_option_duration_ms<'de, D>(d: D) -> Result<Option<Duration>, D::Error>
+pub(crate) fn from_option_duration_ms<'de, D>(d: D) -> Result<Option<Duration>, D::Error>
 where
     D: de::Deserializer<'de>,
 {
@@ -129,7 +131,7 @@ where
 }
 
 /// Serialize `Option<std::time::Duration>` to milliseconds (represented as u64)
-pub(in crate) fn to_option_duration_ms<S>(x: &Option<Duration>, s: S) -> Result<S::Ok, S::Error>
+pub(crate) fn to_option_duration_ms<S>(x: &Option<Duration>, s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -157,6 +159,14 @@ pub struct Followers {
     pub total: u32,
 }
 
+/// Country codes of the markets wrapped by `Vec`
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/markets/get-available-markets/)
+#[derive(Deserialize)]
+pub(crate) struct Markets {
+    pub markets: Vec<String>,
+}
+
 /// A full track object or a full episode object
 ///
 /// + [Reference to full track](https://developer.spotify.com/documentation/web-api/reference/object-model/#track-object-full)
@@ -169,7 +179,7 @@ pub enum PlayingItem {
 }
 
 pub use {
-    album::*, artist::*, audio::*, category::*, context::*, device::*, enums::*, image::*,
-    offset::*, page::*, playing::*, playlist::*, recommend::*, search::*, show::*, track::*,
-    user::*,
+    album::*, artist::*, audio::*, category::*, context::*, device::*, enums::*, idtypes::*,
+    image::*, locale::*, offset::*, page::*, playing::*, playlist::*, recommend::*, search::*,
+    show::*, track::*, user::*,
 };
@@ -34,4 +34,7 @@ pub struct CursorBasedPage<T> {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Cursor {
     pub after: Option<String>,
+    /// The cursor to the older items, only sent by the endpoints that are
+    /// paginated backwards like the recently played tracks.
+    pub before: Option<String>,
 }
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use super::context::Context;
 use super::track::FullTrack;
+use super::PlayingItem;
 
 /// Playing history object
 ///
@@ -14,3 +15,13 @@ pub struct PlayHistory {
     pub played_at: DateTime<Utc>,
     pub context: Option<Context>,
 }
+
+/// The user's playback queue, with the item currently playing and the ones
+/// that will be played next, which may be tracks or episodes.
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/player/get-queue/)
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Queue {
+    pub currently_playing: Option<PlayingItem>,
+    pub queue: Vec<PlayingItem>,
+}
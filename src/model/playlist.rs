@@ -82,6 +82,6 @@ pub struct FeaturedPlaylists {
 ///
 /// [Reference](https://developer.spotify.com/documentation/web-api/reference/browse/get-categorys-playlists/)
 #[derive(Deserialize)]
-pub(in crate) struct CategoryPlaylists {
+pub(crate) struct CategoryPlaylists {
     pub playlists: Page<SimplifiedPlaylist>,
 }
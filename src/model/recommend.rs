@@ -12,6 +12,14 @@ pub struct Recommendations {
     pub tracks: Vec<SimplifiedTrack>,
 }
 
+/// Genre seeds wrapped by `Vec`
+///
+/// [Reference](https://developer.spotify.com/documentation/web-api/reference/browse/get-recommendation-genres/)
+#[derive(Deserialize)]
+pub(crate) struct GenreSeeds {
+    pub genres: Vec<String>,
+}
+
 /// Recommendations seed object
 ///
 /// [Reference](https://developer.spotify.com/documentation/web-api/reference/object-model/#recommendations-seed-object)
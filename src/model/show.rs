@@ -42,7 +42,7 @@ pub struct SimplifiedShow {
 ///
 /// [Reference](https://developer.spotify.com/documentation/web-api/reference/shows/get-several-shows/)
 #[derive(Deserialize)]
-pub(in crate) struct SeversalSimplifiedShows {
+pub(crate) struct SeversalSimplifiedShows {
     pub shows: Vec<SimplifiedShow>,
 }
 
@@ -6,6 +6,7 @@ use std::{collections::HashMap, time::Duration};
 
 use super::album::SimplifiedAlbum;
 use super::artist::SimplifiedArtist;
+use super::idtypes::TrackId;
 use super::Restriction;
 use crate::model::Type;
 use crate::model::{from_duration_ms, to_duration_ms};
@@ -63,7 +64,7 @@ pub struct TrackLink {
 ///
 /// [Reference](https://developer.spotify.com/web-api/get-several-tracks/)
 #[derive(Deserialize)]
-pub(in crate) struct FullTracks {
+pub(crate) struct FullTracks {
     pub tracks: Vec<FullTrack>,
 }
 
@@ -109,3 +110,17 @@ pub struct SavedTrack {
     pub added_at: DateTime<Utc>,
     pub track: FullTrack,
 }
+
+/// Track ID and its positions in a playlist, for removing specific
+/// occurrences of it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TrackPositions {
+    pub id: TrackId,
+    pub positions: Vec<u32>,
+}
+
+impl TrackPositions {
+    pub fn new(id: TrackId, positions: Vec<u32>) -> Self {
+        Self { id, positions }
+    }
+}
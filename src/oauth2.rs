@@ -4,33 +4,106 @@ use chrono::prelude::*;
 use derive_builder::Builder;
 use maybe_async::maybe_async;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use url::Url;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::io::{Read, Write};
-use std::iter::FromIterator;
 use std::path::Path;
+use std::time::Duration;
 
-use super::client::{ClientResult, Spotify};
+use super::client::{ClientError, ClientResult, Spotify};
 use super::http::{headers, BaseClient, Form, Headers};
+use super::scopes::Scopes;
 use super::util::{datetime_to_timestamp, generate_random_string};
 
-mod auth_urls {
-    pub const AUTHORIZE: &str = "https://accounts.spotify.com/authorize";
-    pub const TOKEN: &str = "https://accounts.spotify.com/api/token";
+/// The routes of the Spotify Accounts service, relative to [`Spotify::auth_prefix`
+/// ](../client/struct.Spotify.html#structfield.auth_prefix). A server that
+/// stands in for it has to serve these.
+pub mod auth_urls {
+    /// Where the user is sent to authorize the application.
+    pub const AUTHORIZE: &str = "authorize";
+    /// Where the access tokens are requested and refreshed.
+    pub const TOKEN: &str = "api/token";
 }
 
-// TODO this should be removed after making a custom type for scopes
-// or handling them as a vector of strings.
-fn is_scope_subset(needle_scope: &str, haystack_scope: &str) -> bool {
-    let needle_vec: Vec<&str> = needle_scope.split_whitespace().collect();
-    let haystack_vec: Vec<&str> = haystack_scope.split_whitespace().collect();
-    let needle_set: HashSet<&str> = HashSet::from_iter(needle_vec);
-    let haystack_set: HashSet<&str> = HashSet::from_iter(haystack_vec);
-    // needle_set - haystack_set
-    needle_set.is_subset(&haystack_set)
+/// Generates the PKCE code challenge for the given code verifier, with the
+/// `S256` method: https://tools.ietf.org/html/rfc7636#section-4.2
+fn generate_code_challenge(verifier: &str) -> String {
+    let hash = Sha256::digest(verifier.as_bytes());
+    base64::encode_config(hash, base64::URL_SAFE_NO_PAD)
+}
+
+/// Checks the parameters of the redirect after the authorization, which may
+/// have an error instead, and verifies that their state is the expected one.
+fn check_redirect_params(params: &HashMap<String, String>, state: &str) -> ClientResult<()> {
+    if let Some(error) = params.get("error") {
+        return Err(ClientError::AuthorizationFailed(error.clone()));
+    }
+    if params.get("state").map(String::as_str) != Some(state) {
+        return Err(ClientError::InvalidState);
+    }
+
+    Ok(())
+}
+
+/// Parses the redirect after the authorization, verifying that its state is
+/// the expected one.
+fn parse_redirect(url: &Url, state: &str) -> ClientResult<AuthorizationResponse> {
+    let params: HashMap<_, _> = url.query_pairs().into_owned().collect();
+    check_redirect_params(&params, state)?;
+
+    let code = params
+        .get("code")
+        .ok_or_else(|| ClientError::InvalidAuth("no code in the redirect URL".to_string()))?;
+    Ok(AuthorizationResponse {
+        code: code.clone(),
+        state: state.to_owned(),
+    })
+}
+
+/// Parses the redirect of the [Implicit Grant Flow
+/// ](https://developer.spotify.com/documentation/general/guides/authorization-guide/#implicit-grant-flow),
+/// which has the access token in the fragment of the URL, verifying that its
+/// state is the expected one. Spotify doesn't include the scopes, so the
+/// requested ones are used, since they're granted either all or none.
+fn parse_implicit_redirect(url: &Url, state: &str, scope: &Scopes) -> ClientResult<Token> {
+    let fragment = url.fragment().unwrap_or_default();
+    let mut params: HashMap<_, _> = url::form_urlencoded::parse(fragment.as_bytes())
+        .into_owned()
+        .collect();
+    // Errors may be sent in the query instead
+    params.extend(url.query_pairs().into_owned());
+    check_redirect_params(&params, state)?;
+
+    let mut get = |name: &str| {
+        params
+            .remove(name)
+            .ok_or_else(|| ClientError::InvalidAuth(format!("no {} in the redirect URL", name)))
+    };
+    let access_token = get("access_token")?;
+    let token_type = get("token_type")?;
+    let expires_in = get("expires_in")?.parse().map_err(|_| {
+        ClientError::InvalidAuth("invalid expires_in in the redirect URL".to_string())
+    })?;
+
+    Ok(Token {
+        access_token,
+        token_type,
+        expires_in,
+        expires_at: Some(datetime_to_timestamp(expires_in)),
+        refresh_token: None,
+        scope: scope.clone(),
+    })
+}
+
+/// The parameters in the redirect after the user authorizes the application.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthorizationResponse {
+    pub code: String,
+    pub state: String,
 }
 
 /// Spotify access token information.
@@ -38,13 +111,24 @@ fn is_scope_subset(needle_scope: &str, haystack_scope: &str) -> bool {
 pub struct Token {
     #[builder(setter(into))]
     pub access_token: String,
+    /// How the access token is sent, always `Bearer` for Spotify.
+    #[builder(setter(into), default = "default_token_type()")]
+    #[serde(default = "default_token_type")]
+    pub token_type: String,
     pub expires_in: u32,
     #[builder(setter(strip_option), default)]
     pub expires_at: Option<i64>,
     #[builder(setter(into, strip_option), default)]
     pub refresh_token: Option<String>,
-    #[builder(setter(into))]
-    pub scope: String,
+    /// The scopes granted to the token. Tokens obtained with the client
+    /// credentials flow have none.
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub scope: Scopes,
+}
+
+fn default_token_type() -> String {
+    String::from("Bearer")
 }
 
 impl TokenBuilder {
@@ -56,6 +140,7 @@ impl TokenBuilder {
                 if let Ok(tok) = serde_json::from_str::<Token>(&tok_str) {
                     return TokenBuilder {
                         access_token: Some(tok.access_token),
+                        token_type: Some(tok.token_type),
                         expires_in: Some(tok.expires_in),
                         expires_at: Some(tok.expires_at),
                         refresh_token: Some(tok.refresh_token),
@@ -99,8 +184,11 @@ impl Token {
 pub struct Credentials {
     #[builder(setter(into))]
     pub id: String,
-    #[builder(setter(into))]
-    pub secret: String,
+    /// The client secret, which isn't needed for the [PKCE
+    /// ](https://developer.spotify.com/documentation/general/guides/authorization-guide/#authorization-code-flow-with-proof-key-for-code-exchange-pkce)
+    /// flow, since it can't be stored safely in some applications.
+    #[builder(setter(into, strip_option), default)]
+    pub secret: Option<String>,
 }
 
 impl CredentialsBuilder {
@@ -116,7 +204,7 @@ impl CredentialsBuilder {
 
         CredentialsBuilder {
             id: env::var("RSPOTIFY_CLIENT_ID").ok(),
-            secret: env::var("RSPOTIFY_CLIENT_SECRET").ok(),
+            secret: Some(env::var("RSPOTIFY_CLIENT_SECRET").ok()),
         }
     }
 }
@@ -130,10 +218,36 @@ pub struct OAuth {
     /// https://tools.ietf.org/html/rfc6749#section-10.12
     #[builder(setter(into), default = "generate_random_string(16)")]
     pub state: String,
+    /// The scopes requested when authorizing the application.
     #[builder(setter(into))]
-    pub scope: String,
-    #[builder(setter(into, strip_option), default)]
-    pub proxies: Option<String>,
+    pub scope: Scopes,
+    /// The code verifier for the PKCE flow, generated by default as well. Its
+    /// challenge is sent in the authorization URL, and the verifier itself
+    /// when requesting the access token:
+    /// https://tools.ietf.org/html/rfc7636#section-4.1
+    #[builder(setter(into), default = "generate_random_string(128)")]
+    pub code_verifier: String,
+    /// Whether the CLI methods like `Spotify::prompt_for_user_token` listen
+    /// on the host and port of `redirect_uri` to obtain the code from the
+    /// redirect automatically, instead of asking the user to paste the URL.
+    /// The redirect URI must then point to this machine, like
+    /// `http://localhost:8888/callback`. Disabled by default.
+    #[builder(default)]
+    #[serde(default)]
+    pub callback_server: bool,
+    /// How long the callback server waits for the redirect before giving
+    /// up, [`DEFAULT_CALLBACK_TIMEOUT`](constant.DEFAULT_CALLBACK_TIMEOUT.html)
+    /// by default.
+    #[builder(default = "DEFAULT_CALLBACK_TIMEOUT")]
+    #[serde(default = "default_callback_timeout")]
+    pub callback_timeout: Duration,
+}
+
+/// How long the callback server waits for the redirect by default: 5 minutes.
+pub const DEFAULT_CALLBACK_TIMEOUT: Duration = Duration::from_secs(300);
+
+fn default_callback_timeout() -> Duration {
+    DEFAULT_CALLBACK_TIMEOUT
 }
 
 impl OAuthBuilder {
@@ -153,42 +267,105 @@ impl OAuthBuilder {
     }
 }
 
+/// The authorization flows that start by sending the user to the
+/// authorization URL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AuthorizeFlow {
+    Code,
+    Pkce,
+    Implicit,
+}
+
 /// Authorization-related methods for the client.
 impl Spotify {
-    /// Updates the cache file at the internal cache path.
-    pub fn write_token_cache(&self) -> ClientResult<()> {
-        if let Some(tok) = self.token.as_ref() {
-            tok.write_cache(&self.cache_path)?;
+    /// Saves the current token into the token store, under the client's
+    /// cache key.
+    #[maybe_async]
+    pub async fn write_token_cache(&self) -> ClientResult<()> {
+        let tok = self.token.lock().unwrap().clone();
+        if let Some(tok) = tok {
+            self.token_store.save(&self.cache_key, &tok).await?;
         }
 
         Ok(())
     }
 
+    /// Deletes the token saved in the token store under the client's cache
+    /// key, if any.
+    #[maybe_async]
+    pub async fn delete_token_cache(&self) -> ClientResult<()> {
+        self.token_store.delete(&self.cache_key).await
+    }
+
     /// Gets the required URL to authorize the current client to start the
     /// [Authorization Code Flow](https://developer.spotify.com/documentation/general/guides/authorization-guide/#authorization-code-flow).
     pub fn get_authorize_url(&self, show_dialog: bool) -> ClientResult<String> {
+        self.build_authorize_url(show_dialog, AuthorizeFlow::Code)
+    }
+
+    /// Gets the required URL to authorize the current client to start the
+    /// [Authorization Code Flow with Proof Key for Code Exchange
+    /// ](https://developer.spotify.com/documentation/general/guides/authorization-guide/#authorization-code-flow-with-proof-key-for-code-exchange-pkce),
+    /// which includes the challenge for `OAuth::code_verifier`.
+    pub fn get_authorize_url_pkce(&self, show_dialog: bool) -> ClientResult<String> {
+        self.build_authorize_url(show_dialog, AuthorizeFlow::Pkce)
+    }
+
+    /// Gets the required URL to authorize the current client to start the
+    /// [Implicit Grant Flow
+    /// ](https://developer.spotify.com/documentation/general/guides/authorization-guide/#implicit-grant-flow),
+    /// meant for clients that run entirely in the browser. The access token is
+    /// then obtained from the redirect with
+    /// [`parse_implicit_grant_response`](#method.parse_implicit_grant_response).
+    pub fn get_authorize_url_implicit(&self, show_dialog: bool) -> ClientResult<String> {
+        self.build_authorize_url(show_dialog, AuthorizeFlow::Implicit)
+    }
+
+    fn build_authorize_url(&self, show_dialog: bool, flow: AuthorizeFlow) -> ClientResult<String> {
         let oauth = self.get_oauth()?;
+        let scope = oauth.scope.to_string();
+        let response_type = match flow {
+            AuthorizeFlow::Code | AuthorizeFlow::Pkce => headers::RESPONSE_CODE,
+            AuthorizeFlow::Implicit => headers::RESPONSE_TOKEN,
+        };
         let mut payload: HashMap<&str, &str> = HashMap::new();
         payload.insert(headers::CLIENT_ID, &self.get_creds()?.id);
-        payload.insert(headers::RESPONSE_TYPE, headers::RESPONSE_CODE);
+        payload.insert(headers::RESPONSE_TYPE, response_type);
         payload.insert(headers::REDIRECT_URI, &oauth.redirect_uri);
-        payload.insert(headers::SCOPE, &oauth.scope);
+        payload.insert(headers::SCOPE, &scope);
         payload.insert(headers::STATE, &oauth.state);
 
+        let challenge;
+        if flow == AuthorizeFlow::Pkce {
+            challenge = generate_code_challenge(&oauth.code_verifier);
+            payload.insert(headers::CODE_CHALLENGE, &challenge);
+            payload.insert(
+                headers::CODE_CHALLENGE_METHOD,
+                headers::CODE_CHALLENGE_METHOD_S256,
+            );
+        }
+
         if show_dialog {
             payload.insert(headers::SHOW_DIALOG, "true");
         }
 
-        let parsed = Url::parse_with_params(auth_urls::AUTHORIZE, payload)?;
+        let url = self.auth_prefix.clone() + auth_urls::AUTHORIZE;
+        let parsed = Url::parse_with_params(&url, payload)?;
         Ok(parsed.into_string())
     }
 
-    /// Tries to read the cache file's token, which may not exist.
+    /// Tries to read the token from the token store, which may not exist.
     #[maybe_async]
     pub async fn read_token_cache(&mut self) -> Option<Token> {
-        let tok = TokenBuilder::from_cache(&self.cache_path).build().ok()?;
+        let tok = match self.token_store.load(&self.cache_key).await {
+            Ok(tok) => tok?,
+            Err(err) => {
+                log::warn!("Couldn't read the token cache: {}", err);
+                return None;
+            }
+        };
 
-        if !is_scope_subset(&self.get_oauth().ok()?.scope, &tok.scope) || tok.is_expired() {
+        if !self.get_oauth().ok()?.scope.is_subset(&tok.scope) || tok.is_expired() {
             // Invalid token, since it doesn't have at least the currently
             // required scopes or it's expired.
             None
@@ -202,13 +379,23 @@ impl Spotify {
     async fn fetch_access_token(&self, payload: &Form) -> ClientResult<Token> {
         // This request uses a specific content type, and the client ID/secret
         // as the authentication, since the access token isn't available yet.
+        // Without a secret (PKCE flow), only the client ID is sent, in the
+        // payload.
+        let creds = self.get_creds()?;
         let mut head = Headers::new();
-        let (key, val) = headers::basic_auth(&self.get_creds()?.id, &self.get_creds()?.secret);
-        head.insert(key, val);
+        let mut payload = payload.clone();
+        match creds.secret {
+            Some(ref secret) => {
+                let (key, val) = headers::basic_auth(&creds.id, secret);
+                head.insert(key, val);
+            }
+            None => {
+                payload.insert(headers::CLIENT_ID.to_owned(), creds.id.clone());
+            }
+        }
 
-        let response = self
-            .post_form(auth_urls::TOKEN, Some(&head), payload)
-            .await?;
+        let url = self.auth_prefix.clone() + auth_urls::TOKEN;
+        let response = self.post_form(&url, Some(&head), &payload).await?;
         let mut tok = serde_json::from_str::<Token>(&response)?;
         tok.expires_at = Some(datetime_to_timestamp(tok.expires_in));
 
@@ -219,7 +406,8 @@ impl Spotify {
     /// [Authorization Code Flow](https://developer.spotify.com/documentation/general/guides/authorization-guide/#authorization-code-flow),
     /// without saving it into the cache file.
     ///
-    /// The obtained token will be saved internally.
+    /// The obtained token will be saved internally. This also works for the
+    /// PKCE flow, in which case no client secret is configured.
     #[maybe_async]
     pub async fn refresh_user_token_without_cache(
         &mut self,
@@ -232,9 +420,13 @@ impl Spotify {
             headers::GRANT_REFRESH_TOKEN.to_owned(),
         );
 
+        // Spotify may return a new refresh token, like in the PKCE flow, where
+        // they can only be used once. Otherwise, the same one is kept.
         let mut tok = self.fetch_access_token(&data).await?;
-        tok.refresh_token = Some(refresh_token.to_string());
-        self.token = Some(tok);
+        if tok.refresh_token.is_none() {
+            tok.refresh_token = Some(refresh_token.to_string());
+        }
+        *self.token.lock().unwrap() = Some(tok);
 
         Ok(())
     }
@@ -244,8 +436,7 @@ impl Spotify {
     #[maybe_async]
     pub async fn refresh_user_token(&mut self, refresh_token: &str) -> ClientResult<()> {
         self.refresh_user_token_without_cache(refresh_token).await?;
-
-        Ok(())
+        self.write_token_cache().await
     }
 
     /// Obtains the client access token for the app without saving it into the
@@ -258,7 +449,7 @@ impl Spotify {
             headers::GRANT_CLIENT_CREDS.to_owned(),
         );
 
-        self.token = Some(self.fetch_access_token(&data).await?);
+        *self.token.lock().unwrap() = Some(self.fetch_access_token(&data).await?);
 
         Ok(())
     }
@@ -268,14 +459,70 @@ impl Spotify {
     #[maybe_async]
     pub async fn request_client_token(&mut self) -> ClientResult<()> {
         self.request_client_token_without_cache().await?;
-        self.write_token_cache()
+        self.write_token_cache().await
+    }
+
+    /// Refreshes the current access token in place, using the refresh token
+    /// if available, or the client credentials otherwise. Returns whether the
+    /// token could be refreshed.
+    ///
+    /// This is used internally when automatic token refreshing is enabled, so
+    /// it doesn't take `&mut self`.
+    #[maybe_async]
+    pub(crate) async fn refresh_token(&self) -> ClientResult<bool> {
+        if !self.token_refreshing || self.credentials.is_none() {
+            return Ok(false);
+        }
+
+        let refresh_token = self
+            .token
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|tok| tok.refresh_token.clone());
+
+        let mut data = Form::new();
+        match refresh_token {
+            Some(ref refresh_token) => {
+                data.insert(headers::REFRESH_TOKEN.to_owned(), refresh_token.clone());
+                data.insert(
+                    headers::GRANT_TYPE.to_owned(),
+                    headers::GRANT_REFRESH_TOKEN.to_owned(),
+                );
+            }
+            // A token without a refresh token can only be obtained again
+            // with the client credentials flow, which isn't valid for OAuth.
+            None if self.oauth.is_none() => {
+                data.insert(
+                    headers::GRANT_TYPE.to_owned(),
+                    headers::GRANT_CLIENT_CREDS.to_owned(),
+                );
+            }
+            None => return Ok(false),
+        }
+
+        let mut tok = self.fetch_access_token(&data).await?;
+        if tok.refresh_token.is_none() {
+            tok.refresh_token = refresh_token;
+        }
+        log::info!("Access token refreshed automatically");
+        *self.token.lock().unwrap() = Some(tok.clone());
+
+        // Spotify may have rotated the refresh token, so the stored one
+        // wouldn't be valid anymore. The request can go on anyway.
+        if let Err(err) = self.token_store.save(&self.cache_key, &tok).await {
+            log::warn!("Couldn't save the refreshed token: {}", err);
+        }
+
+        Ok(true)
     }
 
     /// Parse the response code in the given response url. If the URL cannot be
     /// parsed or the `code` parameter is not present, this will return `None`.
     ///
-    /// Step 2 of the [Authorization Code Flow
-    /// ](https://developer.spotify.com/documentation/general/guides/authorization-guide/#authorization-code-flow).
+    /// Note that the state isn't verified, so
+    /// [`parse_authorization_response`](#method.parse_authorization_response)
+    /// should be preferred.
     pub fn parse_response_code(&self, url: &str) -> Option<String> {
         let url = Url::parse(url).ok()?;
         let mut params = url.query_pairs();
@@ -283,6 +530,38 @@ impl Spotify {
         Some(url.to_string())
     }
 
+    /// Parses the URL the user was redirected to after authorizing the
+    /// application, verifying that its state is `OAuth::state`, which
+    /// protects against CSRF attacks:
+    /// https://tools.ietf.org/html/rfc6749#section-10.12
+    ///
+    /// Returns `ClientError::AuthorizationFailed` with the error sent by
+    /// Spotify if the authorization failed, like `access_denied` when the
+    /// user declines it, and `ClientError::InvalidState` if the state doesn't
+    /// match.
+    ///
+    /// Step 2 of the [Authorization Code Flow
+    /// ](https://developer.spotify.com/documentation/general/guides/authorization-guide/#authorization-code-flow).
+    pub fn parse_authorization_response(&self, url: &str) -> ClientResult<AuthorizationResponse> {
+        let url = Url::parse(url)?;
+        parse_redirect(&url, &self.get_oauth()?.state)
+    }
+
+    /// Parses the URL the user was redirected to after authorizing the
+    /// application with the [Implicit Grant Flow
+    /// ](https://developer.spotify.com/documentation/general/guides/authorization-guide/#implicit-grant-flow),
+    /// which has the access token in its fragment. The state is verified and
+    /// errors are returned the same way as in
+    /// [`parse_authorization_response`](#method.parse_authorization_response).
+    ///
+    /// The token isn't saved internally, and it can't be refreshed, since
+    /// there's no refresh token in this flow.
+    pub fn parse_implicit_grant_response(&self, url: &str) -> ClientResult<Token> {
+        let url = Url::parse(url)?;
+        let oauth = self.get_oauth()?;
+        parse_implicit_redirect(&url, &oauth.state, &oauth.scope)
+    }
+
     /// Obtains the user access token for the app with the given code without
     /// saving it into the cache file, as part of the OAuth authentication.
     /// The access token will be saved inside the Spotify instance.
@@ -298,10 +577,10 @@ impl Spotify {
         );
         data.insert(headers::REDIRECT_URI.to_owned(), oauth.redirect_uri.clone());
         data.insert(headers::CODE.to_owned(), code.to_owned());
-        data.insert(headers::SCOPE.to_owned(), oauth.scope.clone());
+        data.insert(headers::SCOPE.to_owned(), oauth.scope.to_string());
         data.insert(headers::STATE.to_owned(), oauth.state.clone());
 
-        self.token = Some(self.fetch_access_token(&data).await?);
+        *self.token.lock().unwrap() = Some(self.fetch_access_token(&data).await?);
 
         Ok(())
     }
@@ -311,7 +590,44 @@ impl Spotify {
     #[maybe_async]
     pub async fn request_user_token(&mut self, code: &str) -> ClientResult<()> {
         self.request_user_token_without_cache(code).await?;
-        self.write_token_cache()
+        self.write_token_cache().await
+    }
+
+    /// Obtains the user access token for the app with the given code without
+    /// saving it into the cache file, as part of the OAuth authentication with
+    /// PKCE. Instead of the client secret, `OAuth::code_verifier` is sent, so
+    /// the authorization URL must have been obtained with
+    /// `get_authorize_url_pkce`. The access token will be saved inside the
+    /// Spotify instance.
+    ///
+    /// Step 3 of the [Authorization Code Flow with Proof Key for Code Exchange
+    /// ](https://developer.spotify.com/documentation/general/guides/authorization-guide/#authorization-code-flow-with-proof-key-for-code-exchange-pkce).
+    #[maybe_async]
+    pub async fn request_user_token_pkce_without_cache(&mut self, code: &str) -> ClientResult<()> {
+        let oauth = self.get_oauth()?;
+        let mut data = Form::new();
+        data.insert(
+            headers::GRANT_TYPE.to_owned(),
+            headers::GRANT_AUTH_CODE.to_owned(),
+        );
+        data.insert(headers::REDIRECT_URI.to_owned(), oauth.redirect_uri.clone());
+        data.insert(headers::CODE.to_owned(), code.to_owned());
+        data.insert(
+            headers::CODE_VERIFIER.to_owned(),
+            oauth.code_verifier.clone(),
+        );
+
+        *self.token.lock().unwrap() = Some(self.fetch_access_token(&data).await?);
+
+        Ok(())
+    }
+
+    /// The same as `request_user_token_pkce_without_cache`, but saves the
+    /// token into the cache file if possible.
+    #[maybe_async]
+    pub async fn request_user_token_pkce(&mut self, code: &str) -> ClientResult<()> {
+        self.request_user_token_pkce_without_cache(code).await?;
+        self.write_token_cache().await
     }
 
     /// Opens up the authorization URL in the user's browser so that it can
@@ -323,7 +639,7 @@ impl Spotify {
     #[cfg(feature = "cli")]
     #[maybe_async]
     pub async fn prompt_for_user_token_without_cache(&mut self) -> ClientResult<()> {
-        let code = self.get_code_from_user()?;
+        let code = self.get_code_from_user(&self.get_authorize_url(false)?)?;
         self.request_user_token_without_cache(&code).await?;
 
         Ok(())
@@ -338,11 +654,13 @@ impl Spotify {
     #[maybe_async]
     pub async fn prompt_for_user_token(&mut self) -> ClientResult<()> {
         // TODO: shouldn't this also refresh the obtained token?
-        self.token = self.read_token_cache().await;
+        let tok = self.read_token_cache().await;
+        let cached = tok.is_some();
+        *self.token.lock().unwrap() = tok;
 
         // Otherwise following the usual procedure to get the token.
-        if self.token.is_none() {
-            let code = self.get_code_from_user()?;
+        if !cached {
+            let code = self.get_code_from_user(&self.get_authorize_url(false)?)?;
             // Will write to the cache file if successful
             self.request_user_token(&code).await?;
         }
@@ -350,17 +668,56 @@ impl Spotify {
         Ok(())
     }
 
-    /// Tries to open the authorization URL in the user's browser, and returns
-    /// the obtained code.
+    /// The same as `prompt_for_user_token_without_cache`, but following the
+    /// PKCE flow, so that the client secret isn't needed.
     ///
     /// Note: this method requires the `cli` feature.
     #[cfg(feature = "cli")]
-    fn get_code_from_user(&self) -> ClientResult<String> {
-        use crate::client::ClientError;
+    #[maybe_async]
+    pub async fn prompt_for_user_token_pkce_without_cache(&mut self) -> ClientResult<()> {
+        let code = self.get_code_from_user(&self.get_authorize_url_pkce(false)?)?;
+        self.request_user_token_pkce_without_cache(&code).await?;
 
-        let url = self.get_authorize_url(false)?;
+        Ok(())
+    }
 
-        match webbrowser::open(&url) {
+    /// The same as `prompt_for_user_token`, but following the PKCE flow, so
+    /// that the client secret isn't needed.
+    ///
+    /// Note: this method requires the `cli` feature.
+    #[cfg(feature = "cli")]
+    #[maybe_async]
+    pub async fn prompt_for_user_token_pkce(&mut self) -> ClientResult<()> {
+        let tok = self.read_token_cache().await;
+        let cached = tok.is_some();
+        *self.token.lock().unwrap() = tok;
+
+        if !cached {
+            let code = self.get_code_from_user(&self.get_authorize_url_pkce(false)?)?;
+            // Will write to the cache file if successful
+            self.request_user_token_pkce(&code).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Tries to open the given authorization URL in the user's browser, and
+    /// returns the obtained code, either from the callback server if
+    /// `OAuth::callback_server` is enabled, or from the URL the user pastes.
+    ///
+    /// Note: this method requires the `cli` feature.
+    #[cfg(feature = "cli")]
+    fn get_code_from_user(&self, url: &str) -> ClientResult<String> {
+        // The listener is started before opening the browser so that the
+        // redirect can't arrive before it's ready.
+        let oauth = self.get_oauth()?;
+        let listener = if oauth.callback_server {
+            Some(callback::CallbackListener::bind(&oauth.redirect_uri)?)
+        } else {
+            None
+        };
+
+        match webbrowser::open(url) {
             Ok(_) => println!("Opened {} in your browser.", url),
             Err(why) => eprintln!(
                 "Error when trying to open an URL in your browser: {:?}. \
@@ -369,54 +726,390 @@ impl Spotify {
             ),
         }
 
+        if let Some(listener) = listener {
+            println!("Waiting for the redirect to {}...", oauth.redirect_uri);
+            return listener.wait_for_code(&oauth.state, oauth.callback_timeout);
+        }
+
         println!("Please enter the URL you were redirected to: ");
         let mut input = String::new();
         std::io::stdin().read_line(&mut input)?;
-        let code = self
-            .parse_response_code(&input)
-            .ok_or_else(|| ClientError::CLI("unable to parse the response code".to_string()))?;
+        let response = self.parse_authorization_response(&input)?;
 
-        Ok(code)
+        Ok(response.code)
+    }
+}
+
+/// A one-shot HTTP server on the redirect URI, which obtains the code from
+/// the redirect after the user authorizes the application in the browser.
+#[cfg(feature = "cli")]
+mod callback {
+    use super::parse_redirect;
+    use crate::client::{ClientError, ClientResult};
+
+    use url::Url;
+
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    const SUCCESS_PAGE: &str = "<html><body><h1>Authorization succeeded</h1>\
+                                <p>You can close this window now.</p></body></html>";
+    const FAILURE_PAGE: &str = "<html><body><h1>Authorization failed</h1>\
+                                <p>Please check the terminal.</p></body></html>";
+    /// How long to wait for a connection to send its request before dropping
+    /// it, since browsers may open connections they never use.
+    const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+    pub struct CallbackListener {
+        listener: TcpListener,
+        path: String,
+        read_timeout: Duration,
+    }
+
+    impl CallbackListener {
+        /// Listens on the host and port of the redirect URI.
+        pub fn bind(redirect_uri: &str) -> ClientResult<Self> {
+            let url = Url::parse(redirect_uri)?;
+            let host = url.host_str().ok_or_else(|| {
+                ClientError::CLI(format!("the redirect URI has no host: {}", redirect_uri))
+            })?;
+            let port = url.port_or_known_default().unwrap_or(80);
+            let listener = TcpListener::bind((host, port))?;
+            // Polling for connections, so that the timeout can be checked
+            listener.set_nonblocking(true)?;
+
+            Ok(CallbackListener {
+                listener,
+                path: url.path().to_owned(),
+                read_timeout: READ_TIMEOUT,
+            })
+        }
+
+        #[cfg(test)]
+        pub fn local_addr(&self) -> std::net::SocketAddr {
+            self.listener.local_addr().unwrap()
+        }
+
+        /// Waits for the redirect, answering it with a page for the user,
+        /// and returns the code in it. Other requests the browser may make,
+        /// like for the favicon, are ignored, and so are the connections that
+        /// fail.
+        pub fn wait_for_code(&self, state: &str, timeout: Duration) -> ClientResult<String> {
+            let deadline = Instant::now() + timeout;
+            loop {
+                let stream = match self.listener.accept() {
+                    Ok((stream, _)) => stream,
+                    Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                        if Instant::now() >= deadline {
+                            return Err(ClientError::CLI(
+                                "timed out waiting for the authorization redirect".to_string(),
+                            ));
+                        }
+                        thread::sleep(Duration::from_millis(50));
+                        continue;
+                    }
+                    Err(err) => return Err(err.into()),
+                };
+
+                match self.handle(stream, state) {
+                    Ok(Some(result)) => return result,
+                    Ok(None) => {}
+                    Err(err) => log::warn!("Dropping a connection to the callback: {}", err),
+                }
+            }
+        }
+
+        /// Answers a single request, returning the result of the
+        /// authorization if it was the redirect.
+        fn handle(
+            &self,
+            mut stream: TcpStream,
+            state: &str,
+        ) -> ClientResult<Option<ClientResult<String>>> {
+            stream.set_nonblocking(false)?;
+            stream.set_read_timeout(Some(self.read_timeout))?;
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line)?;
+            let target = line.split_whitespace().nth(1).unwrap_or_default();
+            // The target is only the path and the query, so a base is needed
+            // for parsing it.
+            let url = Url::parse("http://localhost")?.join(target)?;
+            if url.path() != self.path {
+                respond(&mut stream, "404 Not Found", "")?;
+                return Ok(None);
+            }
+
+            let result = parse_redirect(&url, state).map(|response| response.code);
+
+            let (status, page) = match result {
+                Ok(_) => ("200 OK", SUCCESS_PAGE),
+                Err(_) => ("400 Bad Request", FAILURE_PAGE),
+            };
+            respond(&mut stream, status, page)?;
+
+            Ok(Some(result))
+        }
+    }
+
+    fn respond(stream: &mut TcpStream, status: &str, body: &str) -> ClientResult<()> {
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )?;
+        stream.flush()?;
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        use std::io::Read;
+        use std::net::SocketAddr;
+
+        /// Sends a request to the listener like the browser would, returning
+        /// the response.
+        fn request(addr: SocketAddr, target: &str) -> thread::JoinHandle<String> {
+            let target = target.to_owned();
+            thread::spawn(move || {
+                let mut stream = TcpStream::connect(addr).unwrap();
+                write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", target).unwrap();
+                let mut response = String::new();
+                stream.read_to_string(&mut response).unwrap();
+                response
+            })
+        }
+
+        #[test]
+        fn test_wait_for_code() {
+            let listener = CallbackListener::bind("http://127.0.0.1:0/callback").unwrap();
+            let favicon = request(listener.local_addr(), "/favicon.ico");
+            let redirect = request(listener.local_addr(), "/callback?code=abc&state=xyz");
+
+            let code = listener.wait_for_code("xyz", Duration::from_secs(10));
+            assert_eq!(code.unwrap(), "abc");
+            assert!(favicon.join().unwrap().starts_with("HTTP/1.1 404"));
+            assert!(redirect.join().unwrap().contains("Authorization succeeded"));
+        }
+
+        #[test]
+        fn test_wait_for_code_errors() {
+            let listener = CallbackListener::bind("http://127.0.0.1:0/callback").unwrap();
+            let response = request(listener.local_addr(), "/callback?code=abc&state=other");
+            let code = listener.wait_for_code("xyz", Duration::from_secs(10));
+            assert!(matches!(code, Err(ClientError::InvalidState)));
+            assert!(response.join().unwrap().starts_with("HTTP/1.1 400"));
+
+            let response = request(listener.local_addr(), "/callback?error=access_denied");
+            let code = listener.wait_for_code("xyz", Duration::from_secs(10));
+            assert!(matches!(code, Err(ClientError::AuthorizationFailed(_))));
+            response.join().unwrap();
+
+            let code = listener.wait_for_code("xyz", Duration::from_millis(100));
+            assert!(matches!(code, Err(ClientError::CLI(_))));
+        }
+
+        #[test]
+        fn test_wait_for_code_idle_connection() {
+            let mut listener = CallbackListener::bind("http://127.0.0.1:0/callback").unwrap();
+            listener.read_timeout = Duration::from_millis(100);
+
+            // Browsers may open speculative connections that never send
+            // anything, which are accepted before the redirect.
+            let idle = TcpStream::connect(listener.local_addr()).unwrap();
+            thread::sleep(Duration::from_millis(100));
+            let redirect = request(listener.local_addr(), "/callback?code=abc&state=xyz");
+
+            let code = listener.wait_for_code("xyz", Duration::from_secs(10));
+            assert_eq!(code.unwrap(), "abc");
+            assert!(redirect.join().unwrap().contains("Authorization succeeded"));
+            drop(idle);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::client::SpotifyBuilder;
+    use crate::client::{SpotifyBuilder, DEFAULT_CACHE_PATH};
+    use crate::scopes::Scope;
+    use crate::store::FileTokenStore;
 
     use std::fs;
     use std::io::Read;
 
     #[test]
-    fn test_is_scope_subset() {
-        let mut needle_scope = String::from("1 2 3");
-        let mut haystack_scope = String::from("1 2 3 4");
-        let mut broken_scope = String::from("5 2 4");
-        assert!(is_scope_subset(&mut needle_scope, &mut haystack_scope));
-        assert!(!is_scope_subset(&mut broken_scope, &mut haystack_scope));
+    fn test_generate_code_challenge() {
+        // Example from https://tools.ietf.org/html/rfc7636#appendix-B
+        let verifier = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+        assert_eq!(
+            generate_code_challenge(verifier),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn test_get_authorize_url_pkce() {
+        let oauth = OAuthBuilder::default()
+            .redirect_uri("http://localhost:8888/callback")
+            .scope(Scope::UserReadPrivate)
+            .code_verifier("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk")
+            .build()
+            .unwrap();
+        let creds = CredentialsBuilder::default().id("test-id").build().unwrap();
+        let spotify = SpotifyBuilder::default()
+            .credentials(creds)
+            .oauth(oauth)
+            .build()
+            .unwrap();
+
+        let url = Url::parse(&spotify.get_authorize_url_pkce(false).unwrap()).unwrap();
+        let params: HashMap<_, _> = url.query_pairs().into_owned().collect();
+        assert_eq!(
+            params.get(headers::CODE_CHALLENGE).map(String::as_str),
+            Some("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM")
+        );
+        assert_eq!(
+            params
+                .get(headers::CODE_CHALLENGE_METHOD)
+                .map(String::as_str),
+            Some("S256")
+        );
+
+        let url = Url::parse(&spotify.get_authorize_url(false).unwrap()).unwrap();
+        assert!(url
+            .query_pairs()
+            .all(|(key, _)| key != headers::CODE_CHALLENGE));
+    }
+
+    #[test]
+    fn test_parse_authorization_response() {
+        let oauth = OAuthBuilder::default()
+            .redirect_uri("http://localhost:8888/callback")
+            .scope(Scope::UserReadPrivate)
+            .state("sN")
+            .build()
+            .unwrap();
+        let spotify = SpotifyBuilder::default().oauth(oauth).build().unwrap();
+
+        let url = "http://localhost:8888/callback?code=AQD0yXvFEOvw&state=sN#_=_";
+        let response = spotify.parse_authorization_response(url).unwrap();
+        assert_eq!(response.code, "AQD0yXvFEOvw");
+        assert_eq!(response.state, "sN");
+
+        let url = "http://localhost:8888/callback?code=AQD0yXvFEOvw&state=other";
+        let response = spotify.parse_authorization_response(url);
+        assert!(matches!(response, Err(ClientError::InvalidState)));
+
+        let url = "http://localhost:8888/callback?code=AQD0yXvFEOvw";
+        let response = spotify.parse_authorization_response(url);
+        assert!(matches!(response, Err(ClientError::InvalidState)));
+
+        let url = "http://localhost:8888/callback?error=access_denied&state=sN";
+        let response = spotify.parse_authorization_response(url);
+        assert!(
+            matches!(response, Err(ClientError::AuthorizationFailed(error)) if error == "access_denied")
+        );
+
+        let url = "http://localhost:8888/callback?state=sN";
+        let response = spotify.parse_authorization_response(url);
+        assert!(matches!(response, Err(ClientError::InvalidAuth(_))));
+    }
+
+    #[test]
+    fn test_implicit_grant() {
+        let oauth = OAuthBuilder::default()
+            .redirect_uri("http://localhost:8888/callback")
+            .scope(Scope::UserReadPrivate)
+            .state("sN")
+            .build()
+            .unwrap();
+        let creds = CredentialsBuilder::default().id("test-id").build().unwrap();
+        let spotify = SpotifyBuilder::default()
+            .credentials(creds)
+            .oauth(oauth)
+            .build()
+            .unwrap();
+
+        let url = Url::parse(&spotify.get_authorize_url_implicit(false).unwrap()).unwrap();
+        let params: HashMap<_, _> = url.query_pairs().into_owned().collect();
+        assert_eq!(
+            params.get(headers::RESPONSE_TYPE).map(String::as_str),
+            Some("token")
+        );
+
+        let url = "http://localhost:8888/callback#access_token=NwAExz&token_type=Bearer&expires_in=3600&state=sN";
+        let tok = spotify.parse_implicit_grant_response(url).unwrap();
+        assert_eq!(tok.access_token, "NwAExz");
+        assert_eq!(tok.token_type, "Bearer");
+        assert_eq!(tok.expires_in, 3600);
+        assert!(!tok.is_expired());
+        assert!(tok.refresh_token.is_none());
+        assert_eq!(tok.scope, Scopes::from(Scope::UserReadPrivate));
+
+        let url = "http://localhost:8888/callback#access_token=NwAExz&token_type=Bearer&expires_in=3600&state=other";
+        let tok = spotify.parse_implicit_grant_response(url);
+        assert!(matches!(tok, Err(ClientError::InvalidState)));
+
+        let url = "http://localhost:8888/callback#error=access_denied&state=sN";
+        let tok = spotify.parse_implicit_grant_response(url);
+        assert!(matches!(tok, Err(ClientError::AuthorizationFailed(_))));
+
+        let url = "http://localhost:8888/callback#token_type=Bearer&expires_in=3600&state=sN";
+        let tok = spotify.parse_implicit_grant_response(url);
+        assert!(matches!(tok, Err(ClientError::InvalidAuth(_))));
     }
 
     #[test]
-    fn test_write_token() {
+    fn test_get_authorize_url_auth_prefix() {
+        let oauth = OAuthBuilder::default()
+            .redirect_uri("http://localhost:8888/callback")
+            .scope(Scope::UserReadPrivate)
+            .build()
+            .unwrap();
+        let creds = CredentialsBuilder::default().id("test-id").build().unwrap();
+        let spotify = SpotifyBuilder::default()
+            .credentials(creds)
+            .oauth(oauth)
+            .auth_prefix("http://localhost:8080/accounts/")
+            .build()
+            .unwrap();
+
+        let url = Url::parse(&spotify.get_authorize_url(false).unwrap()).unwrap();
+        assert_eq!(url.host_str(), Some("localhost"));
+        assert_eq!(url.port(), Some(8080));
+        assert_eq!(url.path(), "/accounts/authorize");
+    }
+
+    #[maybe_async]
+    #[cfg_attr(feature = "__async", tokio::test)]
+    #[cfg_attr(feature = "__sync", test)]
+    async fn test_write_token() {
         let tok = TokenBuilder::default()
             .access_token("test-access_token")
             .expires_in(3600)
             .expires_at(1515841743)
-            .scope("playlist-read-private playlist-read-collaborative playlist-modify-public playlist-modify-private streaming ugc-image-upload user-follow-modify user-follow-read user-library-read user-library-modify user-read-private user-read-birthdate user-read-email user-top-read user-read-playback-state user-modify-playback-state user-read-currently-playing user-read-recently-played")
+            .scope(Scopes::all())
             .refresh_token("...")
             .build()
             .unwrap();
 
         let spotify = SpotifyBuilder::default()
             .token(tok.clone())
+            .token_store(FileTokenStore::new(DEFAULT_CACHE_PATH))
             .build()
             .unwrap();
 
         let tok_str = serde_json::to_string(&tok).unwrap();
-        spotify.write_token_cache().unwrap();
+        spotify.write_token_cache().await.unwrap();
 
-        let mut file = fs::File::open(&spotify.cache_path).unwrap();
+        let mut file = fs::File::open(DEFAULT_CACHE_PATH).unwrap();
         let mut tok_str_file = String::new();
         file.read_to_string(&mut tok_str_file).unwrap();
 
@@ -0,0 +1,117 @@
+//! Synchronous implementation of automatic pagination requests.
+
+use crate::client::ClientResult;
+use crate::model::{CursorBasedPage, Page};
+
+/// Alias for `Iterator<Item = ClientResult<T>>`, since sync mode is enabled.
+pub type Paginator<'a, T> = Box<dyn Iterator<Item = ClientResult<T>> + 'a>;
+
+/// Requests the pages of an offset-based endpoint one after another, until
+/// Spotify indicates that there are no more left. `request` is given the
+/// limit and offset of the page to request.
+pub fn paginate<'a, T, Request>(request: Request, page_size: u32) -> Paginator<'a, T>
+where
+    T: 'a,
+    Request: 'a + Fn(u32, u32) -> ClientResult<Page<T>>,
+{
+    let mut next_offset = Some(0);
+    let pages = std::iter::from_fn(move || {
+        let offset = next_offset.take()?;
+        Some(request(page_size, offset).map(|page| {
+            if page.next.is_some() && !page.items.is_empty() {
+                next_offset = Some(offset + page.items.len() as u32);
+            }
+            page.items
+        }))
+    });
+
+    flatten(pages)
+}
+
+/// Requests the pages of a cursor-based endpoint one after another, until
+/// Spotify indicates that there are no more left. `request` is given the
+/// limit and the cursor after which the page starts, if any.
+pub fn paginate_cursor<'a, T, Request>(request: Request, page_size: u32) -> Paginator<'a, T>
+where
+    T: 'a,
+    Request: 'a + Fn(u32, Option<String>) -> ClientResult<CursorBasedPage<T>>,
+{
+    let mut next_after = Some(None);
+    let pages = std::iter::from_fn(move || {
+        let after = next_after.take()?;
+        Some(request(page_size, after).map(|page| {
+            if page.next.is_some() && !page.items.is_empty() {
+                next_after = page.cursors.after.map(Some);
+            }
+            page.items
+        }))
+    });
+
+    flatten(pages)
+}
+
+/// Flattens an iterator of pages into an iterator of their items.
+fn flatten<'a, T: 'a>(pages: impl Iterator<Item = ClientResult<Vec<T>>> + 'a) -> Paginator<'a, T> {
+    Box::new(pages.flat_map(|page| {
+        page.map_or_else(
+            |err| vec![Err(err)],
+            |items| items.into_iter().map(Ok).collect(),
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Cursor;
+
+    fn page(offset: u32, limit: u32, total: u32) -> Page<u32> {
+        let end = total.min(offset + limit);
+        Page {
+            href: String::new(),
+            items: (offset..end).collect(),
+            limit,
+            next: if end < total {
+                Some(String::new())
+            } else {
+                None
+            },
+            offset,
+            previous: None,
+            total,
+        }
+    }
+
+    #[test]
+    fn test_paginate() {
+        let items: Vec<u32> = paginate(|limit, offset| Ok(page(offset, limit, 7)), 3)
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(items, (0..7).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_paginate_cursor() {
+        let items: Vec<u32> = paginate_cursor(
+            |limit, after| {
+                let offset = after.map_or(0, |after| after.parse::<u32>().unwrap());
+                let page = page(offset, limit, 5);
+                Ok(CursorBasedPage {
+                    href: page.href,
+                    next: page.next,
+                    cursors: Cursor {
+                        after: page.items.last().map(|last| (last + 1).to_string()),
+                        before: None,
+                    },
+                    items: page.items,
+                    limit,
+                    total: None,
+                })
+            },
+            2,
+        )
+        .map(Result::unwrap)
+        .collect();
+        assert_eq!(items, (0..5).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,23 @@
+//! Utilities for endpoints that return paginated results. Instead of
+//! requesting each page manually, these lazily request the next page from
+//! Spotify as the items are consumed, until all of them have been returned.
+//!
+//! With asynchronous clients the items are returned as a
+//! [`Stream`](https://docs.rs/futures/0.3/futures/stream/trait.Stream.html),
+//! and with synchronous ones as an [`Iterator`
+//! ](https://doc.rust-lang.org/std/iter/trait.Iterator.html). The paginated
+//! endpoints of the client end with `_stream` or `_iter`, respectively.
+
+#[cfg(feature = "__sync")]
+mod iter;
+#[cfg(feature = "__async")]
+mod stream;
+
+#[cfg(feature = "__sync")]
+pub use iter::{paginate, paginate_cursor, Paginator};
+#[cfg(feature = "__async")]
+pub use stream::{paginate, paginate_cursor, Paginator};
+
+/// The number of items requested per page by the paginated endpoints, which
+/// is the maximum allowed by most of them.
+pub const DEFAULT_PAGINATION_CHUNKS: u32 = 50;
@@ -0,0 +1,141 @@
+//! Asynchronous implementation of automatic pagination requests.
+
+use crate::client::ClientResult;
+use crate::model::{CursorBasedPage, Page};
+
+use std::future::Future;
+use std::pin::Pin;
+
+use futures::stream::{self, Stream, TryStreamExt};
+
+/// Alias for `futures::stream::Stream<Item = ClientResult<T>>`, since async
+/// mode is enabled.
+pub type Paginator<'a, T> = Pin<Box<dyn Stream<Item = ClientResult<T>> + 'a>>;
+
+/// Requests the pages of an offset-based endpoint one after another, until
+/// Spotify indicates that there are no more left. `request` is given the
+/// limit and offset of the page to request.
+pub fn paginate<'a, T, Fut, Request>(request: Request, page_size: u32) -> Paginator<'a, T>
+where
+    T: 'a,
+    Fut: 'a + Future<Output = ClientResult<Page<T>>>,
+    Request: 'a + Fn(u32, u32) -> Fut,
+{
+    let pages = stream::try_unfold(Some(0), move |offset| {
+        let page = offset.map(|offset| (offset, request(page_size, offset)));
+        async move {
+            let (offset, page) = match page {
+                Some(page) => page,
+                None => return Ok(None),
+            };
+
+            let page = page.await?;
+            let next = if page.next.is_some() && !page.items.is_empty() {
+                Some(offset + page.items.len() as u32)
+            } else {
+                None
+            };
+            Ok(Some((page.items, next)))
+        }
+    });
+
+    flatten(pages)
+}
+
+/// Requests the pages of a cursor-based endpoint one after another, until
+/// Spotify indicates that there are no more left. `request` is given the
+/// limit and the cursor after which the page starts, if any.
+pub fn paginate_cursor<'a, T, Fut, Request>(request: Request, page_size: u32) -> Paginator<'a, T>
+where
+    T: 'a,
+    Fut: 'a + Future<Output = ClientResult<CursorBasedPage<T>>>,
+    Request: 'a + Fn(u32, Option<String>) -> Fut,
+{
+    let pages = stream::try_unfold(Some(None), move |after| {
+        let page = after.map(|after| request(page_size, after));
+        async move {
+            let page = match page {
+                Some(page) => page.await?,
+                None => return Ok(None),
+            };
+
+            let next = match page.cursors.after {
+                Some(after) if page.next.is_some() && !page.items.is_empty() => Some(Some(after)),
+                _ => None,
+            };
+            Ok(Some((page.items, next)))
+        }
+    });
+
+    flatten(pages)
+}
+
+/// Flattens a stream of pages into a stream of their items.
+fn flatten<'a, T: 'a>(pages: impl Stream<Item = ClientResult<Vec<T>>> + 'a) -> Paginator<'a, T> {
+    Box::pin(
+        pages
+            .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+            .try_flatten(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Cursor;
+
+    use futures::StreamExt;
+
+    fn page(offset: u32, limit: u32, total: u32) -> Page<u32> {
+        let end = total.min(offset + limit);
+        Page {
+            href: String::new(),
+            items: (offset..end).collect(),
+            limit,
+            next: if end < total {
+                Some(String::new())
+            } else {
+                None
+            },
+            offset,
+            previous: None,
+            total,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_paginate() {
+        let items: Vec<u32> =
+            paginate(|limit, offset| async move { Ok(page(offset, limit, 7)) }, 3)
+                .map(Result::unwrap)
+                .collect()
+                .await;
+        assert_eq!(items, (0..7).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_paginate_cursor() {
+        let items: Vec<u32> = paginate_cursor(
+            |limit, after: Option<String>| async move {
+                let offset = after.map_or(0, |after| after.parse::<u32>().unwrap());
+                let page = page(offset, limit, 5);
+                Ok(CursorBasedPage {
+                    href: page.href,
+                    next: page.next,
+                    cursors: Cursor {
+                        after: page.items.last().map(|last| (last + 1).to_string()),
+                        before: None,
+                    },
+                    items: page.items,
+                    limit,
+                    total: None,
+                })
+            },
+            2,
+        )
+        .map(Result::unwrap)
+        .collect()
+        .await;
+        assert_eq!(items, (0..5).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,218 @@
+//! Client-side rate limiting, so that the client stays under Spotify's quotas
+//! instead of only reacting to `429 Too Many Requests`. The requests of a
+//! client and all its clones go through the same [`RateLimiter`
+//! ](struct.RateLimiter.html), configured with a [`RateLimit`
+//! ](struct.RateLimit.html) in [`SpotifyBuilder::rate_limit`
+//! ](../client/struct.SpotifyBuilder.html#method.rate_limit).
+
+use derive_builder::Builder;
+use maybe_async::maybe_async;
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::http::HttpClient;
+
+/// Configures how the requests are throttled. Each request attempt, including
+/// the retries and the token requests, counts towards the limits.
+#[derive(Builder, Debug, Clone, PartialEq)]
+pub struct RateLimit {
+    /// The maximum number of requests per second on average, following a
+    /// token bucket. No limit by default.
+    #[builder(setter(strip_option), default)]
+    pub requests_per_second: Option<f64>,
+
+    /// The number of requests that can be sent at once after being idle,
+    /// before `requests_per_second` applies. 1 by default.
+    #[builder(default = "1")]
+    pub burst: u32,
+
+    /// The maximum number of requests in flight at the same time. No limit by
+    /// default.
+    #[builder(setter(strip_option), default)]
+    pub max_concurrent: Option<usize>,
+}
+
+/// The requests that went through a [`RateLimiter`
+/// ](struct.RateLimiter.html), and how long they were throttled.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RateLimiterMetrics {
+    /// The number of requests sent.
+    pub requests: u64,
+    /// The number of requests that had to wait before being sent.
+    pub throttled: u64,
+    /// The total time spent waiting by all the requests.
+    pub total_wait: Duration,
+}
+
+/// Applies a [`RateLimit`](struct.RateLimit.html) to the requests of a
+/// client, which is shared between its clones.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    bucket: Mutex<Bucket>,
+    concurrency: Option<Semaphore>,
+    metrics: Mutex<RateLimiterMetrics>,
+}
+
+/// The state of the token bucket. The number of tokens is negative when the
+/// next requests have to wait for them to be refilled.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        RateLimiter {
+            bucket: Mutex::new(Bucket {
+                tokens: f64::from(limit.burst),
+                updated: Instant::now(),
+            }),
+            concurrency: limit.max_concurrent.map(Semaphore::new),
+            metrics: Mutex::new(RateLimiterMetrics::default()),
+            limit,
+        }
+    }
+
+    /// The limits being applied.
+    pub fn limit(&self) -> &RateLimit {
+        &self.limit
+    }
+
+    /// A snapshot of the metrics so far.
+    pub fn metrics(&self) -> RateLimiterMetrics {
+        self.metrics.lock().unwrap().clone()
+    }
+
+    /// Waits until a request can be sent, with the HTTP client so that it
+    /// works with both the async and the blocking ones. The returned permit
+    /// must be kept until the request is done.
+    #[maybe_async]
+    pub(crate) async fn acquire(&self, http: &dyn HttpClient) -> Option<SemaphorePermit<'_>> {
+        let start = Instant::now();
+        let mut permit = None;
+        if let Some(ref concurrency) = self.concurrency {
+            permit = Some(concurrency.acquire().await);
+        }
+        let queued = start.elapsed();
+
+        let delay = self.reserve();
+        if delay > Duration::from_secs(0) {
+            http.sleep(delay).await;
+        }
+
+        // The delay is accounted for as planned instead of measured, since
+        // custom HTTP clients may sleep differently.
+        let wait = queued + delay;
+        let mut metrics = self.metrics.lock().unwrap();
+        metrics.requests += 1;
+        metrics.total_wait += wait;
+        if wait >= Duration::from_millis(1) {
+            metrics.throttled += 1;
+        }
+
+        permit
+    }
+
+    /// Takes a token from the bucket, returning how long to wait until it's
+    /// available.
+    fn reserve(&self) -> Duration {
+        let rate = match self.limit.requests_per_second {
+            Some(rate) if rate > 0.0 => rate,
+            _ => return Duration::from_secs(0),
+        };
+
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        let refilled = now.duration_since(bucket.updated).as_secs_f64() * rate;
+        bucket.tokens = (bucket.tokens + refilled).min(f64::from(self.limit.burst));
+        bucket.updated = now;
+        bucket.tokens -= 1.0;
+
+        if bucket.tokens >= 0.0 {
+            Duration::from_secs(0)
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate)
+        }
+    }
+}
+
+#[cfg(feature = "__async")]
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+#[cfg(feature = "__sync")]
+use self::blocking::{Semaphore, SemaphorePermit};
+
+/// A counting semaphore for the blocking clients, like the one in `tokio`.
+#[cfg(feature = "__sync")]
+mod blocking {
+    use std::sync::{Condvar, Mutex};
+
+    #[derive(Debug)]
+    pub struct Semaphore {
+        available: Mutex<usize>,
+        released: Condvar,
+    }
+
+    /// Releases its slot in the semaphore when dropped.
+    #[derive(Debug)]
+    pub struct SemaphorePermit<'a>(&'a Semaphore);
+
+    impl Semaphore {
+        pub fn new(permits: usize) -> Self {
+            Semaphore {
+                available: Mutex::new(permits),
+                released: Condvar::new(),
+            }
+        }
+
+        pub fn acquire(&self) -> SemaphorePermit<'_> {
+            let mut available = self.available.lock().unwrap();
+            while *available == 0 {
+                available = self.released.wait(available).unwrap();
+            }
+            *available -= 1;
+            SemaphorePermit(self)
+        }
+    }
+
+    impl Drop for SemaphorePermit<'_> {
+        fn drop(&mut self) {
+            *self.0.available.lock().unwrap() += 1;
+            self.0.released.notify_one();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket() {
+        let limit = RateLimitBuilder::default()
+            .requests_per_second(10.0)
+            .burst(2)
+            .build()
+            .unwrap();
+        let limiter = RateLimiter::new(limit);
+
+        assert_eq!(limiter.reserve(), Duration::from_secs(0));
+        assert_eq!(limiter.reserve(), Duration::from_secs(0));
+        // Then each request waits for a tenth of a second more
+        let delay = limiter.reserve();
+        assert!(delay > Duration::from_millis(90) && delay <= Duration::from_millis(100));
+        let delay = limiter.reserve();
+        assert!(delay > Duration::from_millis(190) && delay <= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let limiter = RateLimiter::new(RateLimitBuilder::default().build().unwrap());
+        for _ in 0..100 {
+            assert_eq!(limiter.reserve(), Duration::from_secs(0));
+        }
+    }
+}
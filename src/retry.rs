@@ -0,0 +1,217 @@
+//! Retrying requests that failed temporarily, like when the client is being
+//! rate limited by Spotify or when there's a server error. The client retries
+//! them according to its [`RetryPolicy`](struct.RetryPolicy.html), waiting
+//! longer after each attempt.
+
+use derive_builder::Builder;
+use getrandom::getrandom;
+
+use std::time::Duration;
+
+/// The status codes that are retried by default: `429 Too Many Requests` and
+/// the server errors that are usually temporary.
+pub const DEFAULT_RETRIABLE_STATUSES: [u16; 4] = [429, 502, 503, 504];
+
+/// The status codes that are retried by default for the requests that aren't
+/// idempotent, like `POST`: the ones that guarantee that the request wasn't
+/// processed, `429 Too Many Requests` and `503 Service Unavailable`.
+pub const DEFAULT_NON_IDEMPOTENT_RETRIABLE_STATUSES: [u16; 2] = [429, 503];
+
+/// Configures how the failed requests are retried, with exponential backoff.
+/// If Spotify specifies how long to wait with the `Retry-After` header, that
+/// delay is used instead.
+///
+/// Only the responses with a retriable status are retried. Requests that
+/// couldn't be completed because of transport errors, like timeouts or reset
+/// connections, are never retried, and their error is returned right away.
+///
+/// Requests that aren't idempotent, like the `POST` ones that add tracks to a
+/// playlist or items to the queue, are only retried with the statuses in
+/// `non_idempotent_retriable_statuses`. A `502 Bad Gateway` or a `504 Gateway
+/// Timeout` doesn't mean that the request wasn't processed, so retrying it
+/// could add the same items twice.
+#[derive(Builder, Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of times a request is sent, including the first
+    /// one, so `1` disables retrying. 3 by default.
+    #[builder(default = "3")]
+    pub max_attempts: u32,
+
+    /// The delay before the first retry, which is doubled after each attempt.
+    /// 1 second by default.
+    #[builder(default = "Duration::from_secs(1)")]
+    pub base_delay: Duration,
+
+    /// The maximum delay between attempts. If Spotify asks to wait longer
+    /// than this with `Retry-After`, the request isn't retried and the
+    /// `RateLimited` error is returned instead. 60 seconds by default.
+    #[builder(default = "Duration::from_secs(60)")]
+    pub max_delay: Duration,
+
+    /// Whether a random part of the exponential delay is skipped, so that
+    /// concurrent clients don't retry all at the same time. Enabled by
+    /// default.
+    #[builder(default = "true")]
+    pub jitter: bool,
+
+    /// The status codes of the responses that are retried,
+    /// [`DEFAULT_RETRIABLE_STATUSES`](constant.DEFAULT_RETRIABLE_STATUSES.html)
+    /// by default.
+    #[builder(setter(into), default = "DEFAULT_RETRIABLE_STATUSES.to_vec()")]
+    pub retriable_statuses: Vec<u16>,
+
+    /// The status codes of the responses that are retried for the requests
+    /// that aren't idempotent, [`DEFAULT_NON_IDEMPOTENT_RETRIABLE_STATUSES`
+    /// ](constant.DEFAULT_NON_IDEMPOTENT_RETRIABLE_STATUSES.html) by default.
+    #[builder(
+        setter(into),
+        default = "DEFAULT_NON_IDEMPOTENT_RETRIABLE_STATUSES.to_vec()"
+    )]
+    pub non_idempotent_retriable_statuses: Vec<u16>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicyBuilder::default().build().unwrap()
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries requests.
+    pub fn disabled() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Returns how long to wait before retrying a request that failed with
+    /// `status` in its `attempt`-th try (starting at 1), or `None` if it
+    /// shouldn't be retried. `retry_after` is the value of the `Retry-After`
+    /// header in seconds, if present.
+    pub(crate) fn retry_delay(
+        &self,
+        attempt: u32,
+        status: u16,
+        idempotent: bool,
+        retry_after: Option<u64>,
+    ) -> Option<Duration> {
+        let statuses = if idempotent {
+            &self.retriable_statuses
+        } else {
+            &self.non_idempotent_retriable_statuses
+        };
+        if attempt >= self.max_attempts || !statuses.contains(&status) {
+            return None;
+        }
+
+        if let Some(retry_after) = retry_after {
+            let delay = Duration::from_secs(retry_after);
+            return if delay <= self.max_delay {
+                Some(delay)
+            } else {
+                None
+            };
+        }
+
+        let exponent = (attempt - 1).min(31);
+        let delay = self
+            .base_delay
+            .checked_mul(1 << exponent)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay));
+        // Waiting between half and the whole delay. The whole delay is used if
+        // the OS random number generator fails.
+        let mut buf = [0u8; 4];
+        if self.jitter && getrandom(&mut buf).is_ok() {
+            let half = delay / 2;
+            let random = f64::from(u32::from_le_bytes(buf)) / f64::from(u32::MAX);
+            Some(half + half.mul_f64(random))
+        } else {
+            Some(delay)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicyBuilder::default()
+            .max_attempts(4)
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(3))
+            .jitter(false)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = policy();
+        assert_eq!(
+            policy.retry_delay(1, 503, true, None),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            policy.retry_delay(2, 503, true, None),
+            Some(Duration::from_secs(2))
+        );
+        // Capped by the maximum delay
+        assert_eq!(
+            policy.retry_delay(3, 503, true, None),
+            Some(Duration::from_secs(3))
+        );
+        // No attempts left
+        assert_eq!(policy.retry_delay(4, 503, true, None), None);
+        // Not retriable
+        assert_eq!(policy.retry_delay(1, 404, true, None), None);
+        assert_eq!(
+            RetryPolicy::disabled().retry_delay(1, 429, true, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_non_idempotent() {
+        let policy = policy();
+        assert_eq!(
+            policy.retry_delay(1, 503, false, None),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            policy.retry_delay(1, 429, false, Some(2)),
+            Some(Duration::from_secs(2))
+        );
+        // The request may have been processed
+        assert_eq!(policy.retry_delay(1, 502, false, None), None);
+        assert_eq!(policy.retry_delay(1, 504, false, None), None);
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let policy = policy();
+        assert_eq!(
+            policy.retry_delay(1, 429, true, Some(2)),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            policy.retry_delay(3, 429, true, Some(0)),
+            Some(Duration::from_secs(0))
+        );
+        // Longer than the maximum delay
+        assert_eq!(policy.retry_delay(1, 429, true, Some(10)), None);
+    }
+
+    #[test]
+    fn test_jitter() {
+        let policy = RetryPolicy {
+            jitter: true,
+            ..policy()
+        };
+        for _ in 0..20 {
+            let delay = policy.retry_delay(2, 502, true, None).unwrap();
+            assert!(delay >= Duration::from_secs(1) && delay <= Duration::from_secs(2));
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! Typed authorization scopes, which determine what an access token allows
+//! to do on behalf of the user.
+//!
+//! [Reference](https://developer.spotify.com/documentation/general/guides/scopes/)
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use strum::{AsRefStr, EnumIter, EnumString, IntoEnumIterator, ToString};
+use thiserror::Error;
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+/// Scope parsing error
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ScopeError {
+    /// The scope isn't one of the available [`Scope`](enum.Scope.html)s.
+    #[error("unknown scope: {0}")]
+    Unknown(String),
+}
+
+/// An authorization scope, like `user-read-private`.
+///
+/// [Reference](https://developer.spotify.com/documentation/general/guides/scopes/)
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    ToString,
+    AsRefStr,
+    EnumString,
+    EnumIter,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum Scope {
+    // Images
+    UgcImageUpload,
+    // Spotify Connect
+    UserReadPlaybackState,
+    UserModifyPlaybackState,
+    UserReadCurrentlyPlaying,
+    // Playback
+    Streaming,
+    AppRemoteControl,
+    // Users
+    UserReadEmail,
+    UserReadPrivate,
+    // Playlists
+    PlaylistReadCollaborative,
+    PlaylistModifyPublic,
+    PlaylistReadPrivate,
+    PlaylistModifyPrivate,
+    // Library
+    UserLibraryModify,
+    UserLibraryRead,
+    // Listening history
+    UserTopRead,
+    UserReadPlaybackPosition,
+    UserReadRecentlyPlayed,
+    // Follow
+    UserFollowRead,
+    UserFollowModify,
+}
+
+/// A set of scopes, which is represented by Spotify as a string with the
+/// scopes separated by whitespaces, like `user-read-private user-read-email`.
+///
+/// It can be parsed from that representation with `FromStr`, which fails for
+/// unknown scopes, and it's formatted back with `Display`. When deserialized,
+/// as in the tokens sent by Spotify, unknown scopes are skipped instead, so
+/// that new scopes don't break the authorization.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Scopes(BTreeSet<Scope>);
+
+impl Scopes {
+    /// An empty set of scopes.
+    pub fn new() -> Self {
+        Scopes::default()
+    }
+
+    /// Every available scope.
+    pub fn all() -> Self {
+        Scope::iter().collect()
+    }
+
+    pub fn insert(&mut self, scope: Scope) -> bool {
+        self.0.insert(scope)
+    }
+
+    pub fn remove(&mut self, scope: Scope) -> bool {
+        self.0.remove(&scope)
+    }
+
+    pub fn contains(&self, scope: Scope) -> bool {
+        self.0.contains(&scope)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether all the scopes in this set are also in `other`.
+    pub fn is_subset(&self, other: &Scopes) -> bool {
+        self.0.is_subset(&other.0)
+    }
+
+    /// The scopes in this set that aren't in `other`.
+    pub fn difference(&self, other: &Scopes) -> Scopes {
+        self.0.difference(&other.0).copied().collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Scope> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl fmt::Display for Scopes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scopes: Vec<&str> = self.0.iter().map(AsRef::as_ref).collect();
+        f.write_str(&scopes.join(" "))
+    }
+}
+
+impl FromStr for Scopes {
+    type Err = ScopeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split_whitespace()
+            .map(|scope| Scope::from_str(scope).map_err(|_| ScopeError::Unknown(scope.to_owned())))
+            .collect()
+    }
+}
+
+impl From<Scope> for Scopes {
+    fn from(scope: Scope) -> Self {
+        Some(scope).into_iter().collect()
+    }
+}
+
+impl From<Vec<Scope>> for Scopes {
+    fn from(scopes: Vec<Scope>) -> Self {
+        scopes.into_iter().collect()
+    }
+}
+
+impl FromIterator<Scope> for Scopes {
+    fn from_iter<I: IntoIterator<Item = Scope>>(iter: I) -> Self {
+        Scopes(iter.into_iter().collect())
+    }
+}
+
+impl Extend<Scope> for Scopes {
+    fn extend<I: IntoIterator<Item = Scope>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl IntoIterator for Scopes {
+    type Item = Scope;
+    type IntoIter = std::collections::btree_set::IntoIter<Scope>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Serialize for Scopes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Visitor to deserialize the whitespace-separated scopes, skipping unknown
+/// ones.
+struct ScopesVisitor;
+
+impl<'de> de::Visitor<'de> for ScopesVisitor {
+    type Value = Scopes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a string with whitespace-separated scopes")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v.split_whitespace()
+            .filter_map(|scope| match Scope::from_str(scope) {
+                Ok(scope) => Some(scope),
+                Err(_) => {
+                    log::warn!("Skipping unknown scope: {}", scope);
+                    None
+                }
+            })
+            .collect())
+    }
+}
+
+impl<'de> Deserialize<'de> for Scopes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(ScopesVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scopes() {
+        let scopes: Scopes = "user-read-private  ugc-image-upload\tstreaming"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            scopes,
+            Scopes::from(vec![
+                Scope::UserReadPrivate,
+                Scope::UgcImageUpload,
+                Scope::Streaming
+            ])
+        );
+        assert_eq!(
+            scopes.to_string(),
+            "ugc-image-upload streaming user-read-private"
+        );
+        assert_eq!("".parse::<Scopes>().unwrap(), Scopes::new());
+
+        let err = "user-read-private user-read-birthdate".parse::<Scopes>();
+        assert_eq!(
+            err,
+            Err(ScopeError::Unknown("user-read-birthdate".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_scopes_subset() {
+        let needle = Scopes::from(vec![Scope::UserTopRead, Scope::Streaming]);
+        let haystack = Scopes::from(vec![
+            Scope::UserTopRead,
+            Scope::Streaming,
+            Scope::UserFollowRead,
+        ]);
+        assert!(needle.is_subset(&haystack));
+        assert!(!haystack.is_subset(&needle));
+        assert_eq!(
+            haystack.difference(&needle),
+            Scopes::from(Scope::UserFollowRead)
+        );
+    }
+
+    #[test]
+    fn test_scopes_serde() {
+        let scopes = Scopes::from(vec![Scope::UserReadEmail, Scope::AppRemoteControl]);
+        let json = serde_json::to_string(&scopes).unwrap();
+        assert_eq!(json, r#""app-remote-control user-read-email""#);
+        assert_eq!(serde_json::from_str::<Scopes>(&json).unwrap(), scopes);
+
+        // Unknown scopes are skipped
+        let scopes: Scopes = serde_json::from_str(r#""new-scope user-read-email""#).unwrap();
+        assert_eq!(scopes, Scopes::from(Scope::UserReadEmail));
+    }
+}
@@ -0,0 +1,216 @@
+//! Token persistence, so that the access token can be reused between
+//! sessions. The client saves and loads its tokens through the
+//! [`TokenStore`](trait.TokenStore.html) trait, which can be implemented to
+//! use a custom storage, like a database.
+
+use maybe_async::maybe_async;
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::client::{ClientError, ClientResult};
+use super::oauth2::{Token, TokenBuilder};
+
+/// Storage for access tokens. Each token is saved under a key, which can be
+/// used to identify the user the token belongs to in multi-tenant
+/// applications. The client uses the key configured in
+/// [`Spotify::cache_key`](../client/struct.Spotify.html#structfield.cache_key).
+#[maybe_async]
+pub trait TokenStore: Debug + Send + Sync {
+    /// Loads the token saved under the given key, or `None` if there's no
+    /// token for it.
+    async fn load(&self, key: &str) -> ClientResult<Option<Token>>;
+
+    /// Saves the token under the given key, overwriting the previous one.
+    async fn save(&self, key: &str, token: &Token) -> ClientResult<()>;
+
+    /// Deletes the token saved under the given key, if any.
+    async fn delete(&self, key: &str) -> ClientResult<()>;
+}
+
+/// Saves the token in a single JSON file, which is the default store. Since it
+/// only holds one token, the key is ignored; see [`DirTokenStore`
+/// ](struct.DirTokenStore.html) for multiple users.
+#[derive(Debug, Clone)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    pub fn new<T: Into<PathBuf>>(path: T) -> Self {
+        FileTokenStore { path: path.into() }
+    }
+
+    /// The path of the cache file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[maybe_async]
+impl TokenStore for FileTokenStore {
+    async fn load(&self, _key: &str) -> ClientResult<Option<Token>> {
+        Ok(TokenBuilder::from_cache(&self.path).build().ok())
+    }
+
+    async fn save(&self, _key: &str, token: &Token) -> ClientResult<()> {
+        token.write_cache(&self.path)
+    }
+
+    async fn delete(&self, _key: &str) -> ClientResult<()> {
+        remove_file(&self.path)
+    }
+}
+
+/// Saves each token in a separate JSON file named after its key inside a
+/// directory, which is created if it doesn't exist.
+#[derive(Debug, Clone)]
+pub struct DirTokenStore {
+    dir: PathBuf,
+}
+
+impl DirTokenStore {
+    pub fn new<T: Into<PathBuf>>(dir: T) -> Self {
+        DirTokenStore { dir: dir.into() }
+    }
+
+    /// The path of the cache file for the given key. The key can't be empty
+    /// or contain path separators, so that it doesn't point outside the
+    /// directory.
+    pub fn path(&self, key: &str) -> ClientResult<PathBuf> {
+        let invalid = key.is_empty()
+            || key.starts_with('.')
+            || key.contains(|c| c == '/' || c == '\\' || c == '\0');
+        if invalid {
+            return Err(ClientError::CacheFile(format!(
+                "invalid token store key: {:?}",
+                key
+            )));
+        }
+
+        Ok(self.dir.join(format!("{}.json", key)))
+    }
+}
+
+#[maybe_async]
+impl TokenStore for DirTokenStore {
+    async fn load(&self, key: &str) -> ClientResult<Option<Token>> {
+        Ok(TokenBuilder::from_cache(self.path(key)?).build().ok())
+    }
+
+    async fn save(&self, key: &str, token: &Token) -> ClientResult<()> {
+        let path = self.path(key)?;
+        fs::create_dir_all(&self.dir)?;
+        token.write_cache(path)
+    }
+
+    async fn delete(&self, key: &str) -> ClientResult<()> {
+        remove_file(&self.path(key)?)
+    }
+}
+
+/// Keeps the tokens in memory, so they're lost when the program ends. Useful
+/// for tests, or to share the tokens between clients.
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    tokens: Mutex<HashMap<String, Token>>,
+}
+
+impl MemoryTokenStore {
+    pub fn new() -> Self {
+        MemoryTokenStore::default()
+    }
+}
+
+#[maybe_async]
+impl TokenStore for MemoryTokenStore {
+    async fn load(&self, key: &str) -> ClientResult<Option<Token>> {
+        Ok(self.tokens.lock().unwrap().get(key).cloned())
+    }
+
+    async fn save(&self, key: &str, token: &Token) -> ClientResult<()> {
+        self.tokens
+            .lock()
+            .unwrap()
+            .insert(key.to_owned(), token.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> ClientResult<()> {
+        self.tokens.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+/// Removes a file, considering it's not an error if it doesn't exist.
+fn remove_file(path: &Path) -> ClientResult<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scopes::Scope;
+
+    use maybe_async::maybe_async;
+
+    fn token(access_token: &str) -> Token {
+        TokenBuilder::default()
+            .access_token(access_token)
+            .expires_in(3600)
+            .expires_at(1515841743)
+            .scope(Scope::UserReadPrivate)
+            .build()
+            .unwrap()
+    }
+
+    #[maybe_async]
+    #[cfg_attr(feature = "__async", tokio::test)]
+    #[cfg_attr(feature = "__sync", test)]
+    async fn test_memory_store() {
+        let store = MemoryTokenStore::new();
+        let tok = store.load("user1").await.unwrap();
+        assert!(tok.is_none());
+
+        store.save("user1", &token("token1")).await.unwrap();
+        store.save("user2", &token("token2")).await.unwrap();
+        let tok = store.load("user1").await.unwrap().unwrap();
+        assert_eq!(tok.access_token, "token1");
+
+        store.delete("user1").await.unwrap();
+        let tok = store.load("user1").await.unwrap();
+        assert!(tok.is_none());
+        let tok = store.load("user2").await.unwrap();
+        assert!(tok.is_some());
+    }
+
+    #[maybe_async]
+    #[cfg_attr(feature = "__async", tokio::test)]
+    #[cfg_attr(feature = "__sync", test)]
+    async fn test_dir_store() {
+        let dir = std::env::temp_dir().join("rspotify_test_dir_store");
+        let store = DirTokenStore::new(&dir);
+
+        store.save("user1", &token("token1")).await.unwrap();
+        assert!(dir.join("user1.json").exists());
+        let tok = store.load("user1").await.unwrap().unwrap();
+        assert_eq!(tok.access_token, "token1");
+        let tok = store.load("user2").await.unwrap();
+        assert!(tok.is_none());
+
+        store.delete("user1").await.unwrap();
+        let tok = store.load("user1").await.unwrap();
+        assert!(tok.is_none());
+        // Deleting a missing token isn't an error
+        store.delete("user1").await.unwrap();
+
+        assert!(store.path("../user1").is_err());
+        assert!(store.path("").is_err());
+    }
+}
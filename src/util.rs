@@ -2,13 +2,13 @@ use chrono::prelude::*;
 use getrandom::getrandom;
 
 /// Convert datetime to unix timestamp
-pub(in crate) fn datetime_to_timestamp(elapsed: u32) -> i64 {
+pub(crate) fn datetime_to_timestamp(elapsed: u32) -> i64 {
     let utc: DateTime<Utc> = Utc::now();
     utc.timestamp() + i64::from(elapsed)
 }
 
 /// Generate `length` random chars
-pub(in crate) fn generate_random_string(length: usize) -> String {
+pub(crate) fn generate_random_string(length: usize) -> String {
     let alphanum: &[u8] =
         "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789".as_bytes();
     let mut buf = vec![0u8; length];
@@ -65,3 +65,14 @@ fn test_convert_country_from_str() {
     let zimbabwe = Country::Zimbabwe;
     assert_eq!(zimbabwe.to_string(), "ZW".to_string());
 }
+
+#[test]
+fn test_player_error_reason() {
+    let reason: PlayerErrorReason = serde_json::from_str(r#""NO_ACTIVE_DEVICE""#).unwrap();
+    assert_eq!(reason, PlayerErrorReason::NoActiveDevice);
+    assert_eq!(reason.to_string(), "NO_ACTIVE_DEVICE");
+
+    let reason: PlayerErrorReason = serde_json::from_str(r#""NEW_REASON""#).unwrap();
+    assert_eq!(reason, PlayerErrorReason::Unknown("NEW_REASON".to_owned()));
+    assert_eq!(serde_json::to_string(&reason).unwrap(), r#""NEW_REASON""#);
+}
@@ -835,3 +835,21 @@ fn test_offset() {
     let empty_offset: Offset = serde_json::from_str(&empty_json).unwrap();
     assert!(empty_offset.position.is_none());
 }
+
+#[test]
+fn test_deserialize_ids() {
+    let id: TrackId = serde_json::from_str(r#""4iV5W9uYEdYUVa79Axb7Rh""#).unwrap();
+    assert_eq!(id.id(), "4iV5W9uYEdYUVa79Axb7Rh");
+    assert_eq!(
+        serde_json::to_string(&id).unwrap(),
+        r#""4iV5W9uYEdYUVa79Axb7Rh""#
+    );
+
+    // The IDs are validated like in `from_id`
+    let invalid = serde_json::from_str::<TrackId>(r#""spotify:track:4iV5W9uYEdYUVa79Axb7Rh""#);
+    assert!(invalid.is_err());
+    let invalid = serde_json::from_str::<ArtistId>(r#""""#);
+    assert!(invalid.is_err());
+    let json = r#"{ "id": "not a valid id", "positions": [0] }"#;
+    assert!(serde_json::from_str::<TrackPositions>(json).is_err());
+}
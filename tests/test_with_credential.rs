@@ -2,7 +2,7 @@ mod common;
 
 use common::maybe_async_test;
 use rspotify::client::{Spotify, SpotifyBuilder};
-use rspotify::model::{AlbumType, Country};
+use rspotify::model::{AlbumId, AlbumType, ArtistId, Country, PlaylistId, TrackId, UserId};
 use rspotify::oauth2::CredentialsBuilder;
 
 use maybe_async::maybe_async;
@@ -27,27 +27,28 @@ pub async fn creds_client() -> Spotify {
 #[maybe_async]
 #[maybe_async_test]
 async fn test_album() {
-    let birdy_uri = "spotify:album:0sNOF9WDwhWunNAHPD3Baj";
-    creds_client().await.album(birdy_uri).await.unwrap();
+    let birdy_uri = AlbumId::from_uri("spotify:album:0sNOF9WDwhWunNAHPD3Baj").unwrap();
+    creds_client().await.album(&birdy_uri).await.unwrap();
 }
 
 #[maybe_async]
 #[maybe_async_test]
 async fn test_albums() {
-    let birdy_uri1 = "spotify:album:41MnTivkwTO3UUJ8DrqEJJ";
-    let birdy_uri2 = "spotify:album:6JWc4iAiJ9FjyK0B59ABb4";
-    let birdy_uri3 = "spotify:album:6UXCm6bOO4gFlDQZV5yL37";
-    let track_uris = vec![birdy_uri1, birdy_uri2, birdy_uri3];
-    creds_client().await.albums(track_uris).await.unwrap();
+    let track_uris = vec![
+        AlbumId::from_uri("spotify:album:41MnTivkwTO3UUJ8DrqEJJ").unwrap(),
+        AlbumId::from_uri("spotify:album:6JWc4iAiJ9FjyK0B59ABb4").unwrap(),
+        AlbumId::from_uri("spotify:album:6UXCm6bOO4gFlDQZV5yL37").unwrap(),
+    ];
+    creds_client().await.albums(&track_uris).await.unwrap();
 }
 
 #[maybe_async]
 #[maybe_async_test]
 async fn test_album_tracks() {
-    let birdy_uri = "spotify:album:6akEvsycLGftJxYudPjmqK";
+    let birdy_uri = AlbumId::from_uri("spotify:album:6akEvsycLGftJxYudPjmqK").unwrap();
     creds_client()
         .await
-        .album_track(birdy_uri, Some(2), None)
+        .album_track(&birdy_uri, Some(2), None)
         .await
         .unwrap();
 }
@@ -55,10 +56,10 @@ async fn test_album_tracks() {
 #[maybe_async]
 #[maybe_async_test]
 async fn test_artist_related_artists() {
-    let birdy_uri = "spotify:artist:43ZHCT0cAZBISjO8DG9PnE";
+    let birdy_uri = ArtistId::from_uri("spotify:artist:43ZHCT0cAZBISjO8DG9PnE").unwrap();
     creds_client()
         .await
-        .artist_related_artists(birdy_uri)
+        .artist_related_artists(&birdy_uri)
         .await
         .unwrap();
 }
@@ -66,18 +67,18 @@ async fn test_artist_related_artists() {
 #[maybe_async]
 #[maybe_async_test]
 async fn test_artist() {
-    let birdy_uri = "spotify:artist:2WX2uTcsvV5OnS0inACecP";
-    creds_client().await.artist(birdy_uri).await.unwrap();
+    let birdy_uri = ArtistId::from_uri("spotify:artist:2WX2uTcsvV5OnS0inACecP").unwrap();
+    creds_client().await.artist(&birdy_uri).await.unwrap();
 }
 
 #[maybe_async]
 #[maybe_async_test]
 async fn test_artists_albums() {
-    let birdy_uri = "spotify:artist:2WX2uTcsvV5OnS0inACecP";
+    let birdy_uri = ArtistId::from_uri("spotify:artist:2WX2uTcsvV5OnS0inACecP").unwrap();
     creds_client()
         .await
         .artist_albums(
-            birdy_uri,
+            &birdy_uri,
             Some(AlbumType::Album),
             Some(Country::UnitedStates),
             Some(10),
@@ -90,19 +91,20 @@ async fn test_artists_albums() {
 #[maybe_async]
 #[maybe_async_test]
 async fn test_artists() {
-    let birdy_uri1 = "spotify:artist:0oSGxfWSnnOXhD2fKuz2Gy";
-    let birdy_uri2 = "spotify:artist:3dBVyJ7JuOMt4GE9607Qin";
-    let artist_uris = vec![birdy_uri1, birdy_uri2];
-    creds_client().await.artists(artist_uris).await.unwrap();
+    let artist_uris = vec![
+        ArtistId::from_uri("spotify:artist:0oSGxfWSnnOXhD2fKuz2Gy").unwrap(),
+        ArtistId::from_uri("spotify:artist:3dBVyJ7JuOMt4GE9607Qin").unwrap(),
+    ];
+    creds_client().await.artists(&artist_uris).await.unwrap();
 }
 
 #[maybe_async]
 #[maybe_async_test]
 async fn test_artist_top_tracks() {
-    let birdy_uri = "spotify:artist:2WX2uTcsvV5OnS0inACecP";
+    let birdy_uri = ArtistId::from_uri("spotify:artist:2WX2uTcsvV5OnS0inACecP").unwrap();
     creds_client()
         .await
-        .artist_top_tracks(birdy_uri, Country::UnitedStates)
+        .artist_top_tracks(&birdy_uri, Country::UnitedStates)
         .await
         .unwrap();
 }
@@ -110,28 +112,27 @@ async fn test_artist_top_tracks() {
 #[maybe_async]
 #[maybe_async_test]
 async fn test_audio_analysis() {
-    let track = "06AKEBrKUckW0KREUWRnvT";
-    creds_client().await.track_analysis(track).await.unwrap();
+    let track = TrackId::from_id("06AKEBrKUckW0KREUWRnvT").unwrap();
+    creds_client().await.track_analysis(&track).await.unwrap();
 }
 
 #[maybe_async]
 #[maybe_async_test]
 async fn test_audio_features() {
-    let track = "spotify:track:06AKEBrKUckW0KREUWRnvT";
-    creds_client().await.track_features(track).await.unwrap();
+    let track = TrackId::from_uri("spotify:track:06AKEBrKUckW0KREUWRnvT").unwrap();
+    creds_client().await.track_features(&track).await.unwrap();
 }
 
 #[maybe_async]
 #[maybe_async_test]
 async fn test_audios_features() {
-    let mut tracks_ids = vec![];
-    let track_id1 = "spotify:track:4JpKVNYnVcJ8tuMKjAj50A";
-    tracks_ids.push(track_id1);
-    let track_id2 = "spotify:track:24JygzOLM0EmRQeGtFcIcG";
-    tracks_ids.push(track_id2);
+    let tracks_ids = vec![
+        TrackId::from_uri("spotify:track:4JpKVNYnVcJ8tuMKjAj50A").unwrap(),
+        TrackId::from_uri("spotify:track:24JygzOLM0EmRQeGtFcIcG").unwrap(),
+    ];
     creds_client()
         .await
-        .tracks_features(tracks_ids)
+        .tracks_features(&tracks_ids)
         .await
         .unwrap();
 }
@@ -139,24 +140,29 @@ async fn test_audios_features() {
 #[maybe_async]
 #[maybe_async_test]
 async fn test_user() {
-    let birdy_uri = String::from("tuggareutangranser");
+    let birdy_uri = UserId::from_id("tuggareutangranser").unwrap();
     creds_client().await.user(&birdy_uri).await.unwrap();
 }
 
 #[maybe_async]
 #[maybe_async_test]
 async fn test_track() {
-    let birdy_uri = "spotify:track:6rqhFgbbKwnb9MLmUQDhG6";
-    creds_client().await.track(birdy_uri).await.unwrap();
+    let birdy_uri = TrackId::from_uri("spotify:track:6rqhFgbbKwnb9MLmUQDhG6").unwrap();
+    creds_client().await.track(&birdy_uri).await.unwrap();
 }
 
 #[maybe_async]
 #[maybe_async_test]
 async fn test_tracks() {
-    let birdy_uri1 = "spotify:track:3n3Ppam7vgaVa1iaRUc9Lp";
-    let birdy_uri2 = "spotify:track:3twNvmDtFQtAd5gMKedhLD";
-    let track_uris = vec![birdy_uri1, birdy_uri2];
-    creds_client().await.tracks(track_uris, None).await.unwrap();
+    let track_uris = vec![
+        TrackId::from_uri("spotify:track:3n3Ppam7vgaVa1iaRUc9Lp").unwrap(),
+        TrackId::from_uri("spotify:track:3twNvmDtFQtAd5gMKedhLD").unwrap(),
+    ];
+    creds_client()
+        .await
+        .tracks(&track_uris, None)
+        .await
+        .unwrap();
 }
 
 #[maybe_async]
@@ -164,7 +170,11 @@ async fn test_tracks() {
 async fn test_existing_playlist() {
     creds_client()
         .await
-        .playlist("37i9dQZF1DZ06evO45P0Eo", None, None)
+        .playlist(
+            &PlaylistId::from_id("37i9dQZF1DZ06evO45P0Eo").unwrap(),
+            None,
+            None,
+        )
         .await
         .unwrap();
 }
@@ -172,6 +182,10 @@ async fn test_existing_playlist() {
 #[maybe_async]
 #[maybe_async_test]
 async fn test_fake_playlist() {
-    let playlist = creds_client().await.playlist("fake_id", None, None).await;
+    let playlist_id = PlaylistId::from_id("fakeid").unwrap();
+    let playlist = creds_client()
+        .await
+        .playlist(&playlist_id, None, None)
+        .await;
     assert!(!playlist.is_ok());
 }
@@ -0,0 +1,812 @@
+//! These tests run the whole client against the mock server in the `mock`
+//! module, so they don't need any credentials or network access. Enable the
+//! `mock-server` feature to run them:
+//!
+//! ```
+//! $ cargo test --features=mock-server
+//! ```
+
+mod common;
+
+use common::maybe_async_test;
+use rspotify::client::{ClientError, Spotify, MAX_COVER_IMAGE_SIZE};
+use rspotify::http::{Body, Headers, Method, Query};
+use rspotify::mock::{
+    MockServer, MOCK_ACCESS_TOKEN, MOCK_AUTH_CODE, MOCK_PAGE_TOTAL, MOCK_REFRESH_TOKEN,
+};
+use rspotify::model::offset::for_position;
+use rspotify::model::{
+    AlbumId, ArtistId, Country, EpisodeId, Id, Locale, PlayableId, PlayingItem, PlaylistId,
+    RepeatState, SearchResult, SearchType, ShowId, TrackId, TrackPositions, UserId,
+};
+use rspotify::oauth2::{OAuthBuilder, TokenBuilder};
+use rspotify::scopes::{Scope, Scopes};
+use rspotify::store::MemoryTokenStore;
+
+use maybe_async::maybe_async;
+use serde_json::{json, map::Map, Value};
+
+/// Generating a client with a token obtained from the mock server.
+#[maybe_async]
+async fn mock_client(server: &MockServer) -> Spotify {
+    let mut spotify = server.client_builder().build().unwrap();
+    spotify.request_client_token().await.unwrap();
+    spotify
+}
+
+fn track_ids() -> Vec<TrackId> {
+    vec![
+        TrackId::from_id("4iV5W9uYEdYUVa79Axb7Rh").unwrap(),
+        TrackId::from_id("1301WleyT98MSxVHPZCA6M").unwrap(),
+    ]
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_client_token() {
+    let server = MockServer::start().unwrap();
+    let spotify = mock_client(&server).await;
+    let token = spotify.token.lock().unwrap().clone().unwrap();
+    assert_eq!(token.access_token, MOCK_ACCESS_TOKEN);
+
+    let request = &server.requests()[0];
+    assert_eq!(request.path, "/api/token");
+    assert_eq!(
+        request.form_param("grant_type").as_deref(),
+        Some("client_credentials")
+    );
+    assert!(request.headers["authorization"].starts_with("Basic "));
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_user_token() {
+    let server = MockServer::start().unwrap();
+    let oauth = OAuthBuilder::default()
+        .redirect_uri("http://localhost:8888/callback")
+        .scope(Scope::UserReadPrivate)
+        .build()
+        .unwrap();
+    let mut spotify = server.client_builder().oauth(oauth).build().unwrap();
+
+    let url = spotify.get_authorize_url(false).unwrap();
+    assert!(url.starts_with(&format!("{}authorize?", server.url())));
+
+    spotify
+        .request_user_token_without_cache(MOCK_AUTH_CODE)
+        .await
+        .unwrap();
+    let token = spotify.token.lock().unwrap().clone().unwrap();
+    assert_eq!(token.refresh_token.as_deref(), Some(MOCK_REFRESH_TOKEN));
+
+    spotify
+        .refresh_user_token_without_cache(MOCK_REFRESH_TOKEN)
+        .await
+        .unwrap();
+    let grants: Vec<_> = server
+        .requests()
+        .iter()
+        .map(|request| request.form_param("grant_type").unwrap())
+        .collect();
+    assert_eq!(grants, vec!["authorization_code", "refresh_token"]);
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_refresh_token_rotation() {
+    let server = MockServer::start().unwrap();
+    let mut spotify = server.client_builder().build().unwrap();
+
+    // The refresh token returned by Spotify replaces the one that was used
+    spotify
+        .refresh_user_token_without_cache("used-refresh-token")
+        .await
+        .unwrap();
+    let token = spotify.token.lock().unwrap().clone().unwrap();
+    assert_eq!(token.refresh_token.as_deref(), Some(MOCK_REFRESH_TOKEN));
+    let request = server.requests().pop().unwrap();
+    assert_eq!(
+        request.form_param("refresh_token").as_deref(),
+        Some("used-refresh-token")
+    );
+
+    // And it's saved into the store when using the cache
+    spotify
+        .refresh_user_token("used-refresh-token")
+        .await
+        .unwrap();
+    let stored = spotify
+        .token_store
+        .load(&spotify.cache_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.refresh_token.as_deref(), Some(MOCK_REFRESH_TOKEN));
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_automatic_refresh() {
+    let server = MockServer::start().unwrap();
+    let expired = TokenBuilder::default()
+        .access_token("expired-access-token")
+        .expires_in(3600)
+        .expires_at(0)
+        .refresh_token("used-refresh-token")
+        .build()
+        .unwrap();
+    let spotify = server
+        .client_builder()
+        .token(expired)
+        .token_store(MemoryTokenStore::new())
+        .build()
+        .unwrap();
+
+    // The expired token is refreshed before the request, and saved into the
+    // store with the rotated refresh token
+    spotify.current_user().await.unwrap();
+    let token = spotify
+        .token_store
+        .load(&spotify.cache_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(token.access_token, MOCK_ACCESS_TOKEN);
+    assert_eq!(token.refresh_token.as_deref(), Some(MOCK_REFRESH_TOKEN));
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_missing_scopes() {
+    let server = MockServer::start().unwrap();
+    let oauth = OAuthBuilder::default()
+        .redirect_uri("http://localhost:8888/callback")
+        .scope(vec![Scope::UserReadPrivate, Scope::UserTopRead])
+        .build()
+        .unwrap();
+    let mut spotify = server.client_builder().oauth(oauth).build().unwrap();
+    spotify
+        .request_user_token_without_cache(MOCK_AUTH_CODE)
+        .await
+        .unwrap();
+
+    spotify.current_user_top_artists(10, 0, None).await.unwrap();
+    let result = spotify.current_user_saved_tracks(10, 0).await;
+    assert!(
+        matches!(result, Err(ClientError::MissingScopes(missing)) if missing == Scopes::from(Scope::UserLibraryRead))
+    );
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_missing_any_scope() {
+    let server = MockServer::start().unwrap();
+    let playlist_id = PlaylistId::from_id("37i9dQZF1DXcBWIGoYBM5M").unwrap();
+    let image = b"\xff\xd8\xff\xe0mock";
+
+    // Uploading a cover requires either of the playlist modification scopes
+    for (scope, allowed) in &[
+        (vec![Scope::UgcImageUpload], false),
+        (
+            vec![Scope::UgcImageUpload, Scope::PlaylistModifyPrivate],
+            true,
+        ),
+        (
+            vec![Scope::UgcImageUpload, Scope::PlaylistModifyPublic],
+            true,
+        ),
+    ] {
+        let oauth = OAuthBuilder::default()
+            .redirect_uri("http://localhost:8888/callback")
+            .scope(scope.clone())
+            .build()
+            .unwrap();
+        let mut spotify = server.client_builder().oauth(oauth).build().unwrap();
+        spotify
+            .request_user_token_without_cache(MOCK_AUTH_CODE)
+            .await
+            .unwrap();
+
+        let result = spotify
+            .playlist_upload_cover_image(&playlist_id, image)
+            .await;
+        if *allowed {
+            result.unwrap();
+        } else {
+            let expected = Scopes::from(vec![
+                Scope::PlaylistModifyPublic,
+                Scope::PlaylistModifyPrivate,
+            ]);
+            assert!(
+                matches!(result, Err(ClientError::MissingScopes(missing)) if missing == expected)
+            );
+        }
+    }
+
+    // The rest of the playlist modifications, and the endpoints that accept
+    // other alternative scopes
+    let oauth = OAuthBuilder::default()
+        .redirect_uri("http://localhost:8888/callback")
+        .scope(Scope::UserReadPlaybackState)
+        .build()
+        .unwrap();
+    let mut spotify = server.client_builder().oauth(oauth).build().unwrap();
+    spotify
+        .request_user_token_without_cache(MOCK_AUTH_CODE)
+        .await
+        .unwrap();
+    server.clear_requests();
+
+    spotify.current_user_playing_track().await.unwrap();
+    let result = spotify
+        .playlist_add_tracks(&playlist_id, &track_ids(), None)
+        .await;
+    assert!(matches!(result, Err(ClientError::MissingScopes(_))));
+    let result = spotify.playlist_unfollow(&playlist_id).await;
+    assert!(matches!(result, Err(ClientError::MissingScopes(_))));
+    let result = spotify.current_user_playlists(10, None).await;
+    assert!(matches!(result, Err(ClientError::MissingScopes(_))));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_unauthorized() {
+    let server = MockServer::start().unwrap();
+    let spotify = server.client_builder().build().unwrap();
+    let id = ArtistId::from_id("0OdUWJ0sBjDrqHygGUXeCF").unwrap();
+    let result = spotify.artist(&id).await;
+    assert!(matches!(result, Err(ClientError::InvalidAuth(_))));
+    assert!(server.requests().is_empty());
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_invalid_header() {
+    let server = MockServer::start().unwrap();
+    let spotify = mock_client(&server).await;
+    server.clear_requests();
+
+    // Headers from the caller may be invalid, which fails before the request
+    let mut headers = Headers::new();
+    headers.insert(
+        "If-None-Match".to_owned(),
+        "\"abc\"\r\nX-Injected: 1".to_owned(),
+    );
+    let result = spotify
+        .api_response(Method::Get, "me", &headers, &Query::new(), Body::Empty)
+        .await;
+    assert!(matches!(result, Err(ClientError::Request(_))));
+    assert!(server.requests().is_empty());
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_tracks_artists_albums() {
+    let server = MockServer::start().unwrap();
+    let spotify = mock_client(&server).await;
+    let track_ids = track_ids();
+
+    let track = spotify.track(&track_ids[0]).await.unwrap();
+    assert_eq!(track.id.as_deref(), Some("4iV5W9uYEdYUVa79Axb7Rh"));
+    let tracks = spotify.tracks(&track_ids, None).await.unwrap();
+    assert_eq!(tracks.len(), 2);
+    assert_eq!(tracks[1].id.as_deref(), Some("1301WleyT98MSxVHPZCA6M"));
+
+    let artist_id = ArtistId::from_id("0OdUWJ0sBjDrqHygGUXeCF").unwrap();
+    let artist = spotify.artist(&artist_id).await.unwrap();
+    assert_eq!(artist.id, artist_id.id());
+    let artists = spotify.artists(vec![&artist_id]).await.unwrap();
+    assert_eq!(artists.len(), 1);
+    let albums = spotify
+        .artist_albums(&artist_id, None, None, Some(2), None)
+        .await
+        .unwrap();
+    assert_eq!(albums.items.len(), 2);
+    assert_eq!(albums.total, MOCK_PAGE_TOTAL);
+    assert!(albums.next.is_some());
+    spotify.artist_top_tracks(&artist_id, None).await.unwrap();
+    spotify.artist_related_artists(&artist_id).await.unwrap();
+
+    let album_id = AlbumId::from_id("6akEvsycLGftJxYudPjmqK").unwrap();
+    let album = spotify.album(&album_id).await.unwrap();
+    assert_eq!(album.id, album_id.id());
+    let albums = spotify.albums(vec![&album_id]).await.unwrap();
+    assert_eq!(albums.len(), 1);
+    let page = spotify.album_track(&album_id, 2, 4).await.unwrap();
+    assert_eq!(page.items.len() as u32, MOCK_PAGE_TOTAL - 4);
+    assert!(page.next.is_none());
+
+    spotify.track_features(&track_ids[0]).await.unwrap();
+    let features = spotify.tracks_features(&track_ids).await.unwrap().unwrap();
+    assert_eq!(features.len(), 2);
+    spotify.track_analysis(&track_ids[0]).await.unwrap();
+
+    let result = spotify
+        .search("mock", SearchType::Artist, 10, 0, None, None)
+        .await
+        .unwrap();
+    assert!(matches!(result, SearchResult::Artists(_)));
+    let result = spotify
+        .search("mock", SearchType::Episode, 10, 0, None, None)
+        .await
+        .unwrap();
+    assert!(matches!(result, SearchResult::Episodes(_)));
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_chunked_ids() {
+    let server = MockServer::start().unwrap();
+    let mut spotify = mock_client(&server).await;
+    let track_ids: Vec<TrackId> = (0..120)
+        .map(|i| TrackId::from_id(&format!("mocktrack{:013}", i)).unwrap())
+        .collect();
+    let expected: Vec<&str> = track_ids.iter().map(Id::id).collect();
+
+    // The IDs are split in chunks of at most 50, and the tracks are returned
+    // in the same order
+    server.clear_requests();
+    let tracks = spotify.tracks(&track_ids, None).await.unwrap();
+    let ids: Vec<&str> = tracks.iter().filter_map(|t| t.id.as_deref()).collect();
+    assert_eq!(ids, expected);
+    let sizes: Vec<usize> = server
+        .requests()
+        .iter()
+        .map(|request| request.param("ids").unwrap().split(',').count())
+        .collect();
+    assert_eq!(sizes, vec![50, 50, 20]);
+
+    // Also when requested concurrently
+    spotify.concurrent_chunks = true;
+    let tracks = spotify.tracks(&track_ids, None).await.unwrap();
+    let ids: Vec<&str> = tracks.iter().filter_map(|t| t.id.as_deref()).collect();
+    assert_eq!(ids, expected);
+
+    let album_ids: Vec<AlbumId> = (0..45)
+        .map(|i| AlbumId::from_id(&format!("mockalbum{:013}", i)).unwrap())
+        .collect();
+    let albums = spotify.albums(&album_ids).await.unwrap();
+    assert_eq!(albums.len(), 45);
+
+    // The features are merged in order, and a chunk without data fails
+    // instead of shifting the rest
+    let features = spotify.tracks_features(&track_ids).await.unwrap().unwrap();
+    let ids: Vec<&str> = features.iter().map(|f| f.id.as_str()).collect();
+    assert_eq!(ids, expected);
+    let track_ids: Vec<TrackId> = (0..250)
+        .map(|i| match i {
+            100..=199 => format!("mocknofeatures{:08}", i),
+            _ => format!("mocktrack{:013}", i),
+        })
+        .map(|id| TrackId::from_id(&id).unwrap())
+        .collect();
+    let result = spotify.tracks_features(&track_ids).await;
+    assert!(matches!(result, Err(ClientError::EmptyChunk(100))));
+    let result = spotify.tracks_features(&track_ids[100..200]).await;
+    assert!(matches!(result, Ok(None)));
+
+    // Replacing the tracks of a playlist replaces them with the first chunk
+    // and then adds the rest in order
+    server.clear_requests();
+    let track_ids: Vec<TrackId> = (0..250)
+        .map(|i| TrackId::from_id(&format!("mocktrack{:013}", i)).unwrap())
+        .collect();
+    let playlist_id = PlaylistId::from_id("37i9dQZF1DXcBWIGoYBM5M").unwrap();
+    spotify
+        .playlist_replace_tracks(&playlist_id, &track_ids)
+        .await
+        .unwrap();
+    let requests = server.requests();
+    let methods: Vec<&str> = requests.iter().map(|r| r.method.as_str()).collect();
+    assert_eq!(methods, vec!["PUT", "POST", "POST"]);
+    let sizes: Vec<usize> = requests
+        .iter()
+        .map(|request| request.json()["uris"].as_array().unwrap().len())
+        .collect();
+    assert_eq!(sizes, vec![100, 100, 50]);
+    let uris: Vec<Value> = requests
+        .iter()
+        .flat_map(|request| request.json()["uris"].as_array().unwrap().clone())
+        .collect();
+    let expected: Vec<Value> = track_ids.iter().map(|id| id.uri().into()).collect();
+    assert_eq!(uris, expected);
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_playlists() {
+    let server = MockServer::start().unwrap();
+    let spotify = mock_client(&server).await;
+    let user_id = UserId::from_id("mockuser").unwrap();
+    let playlist_id = PlaylistId::from_id("37i9dQZF1DXcBWIGoYBM5M").unwrap();
+    let track_ids = track_ids();
+
+    spotify.user(&user_id).await.unwrap();
+    let playlist = spotify.playlist(&playlist_id, None, None).await.unwrap();
+    assert_eq!(playlist.id, playlist_id.id());
+    spotify.current_user_playlists(10, None).await.unwrap();
+    spotify.user_playlists(&user_id, 10, None).await.unwrap();
+    spotify
+        .user_playlist(&user_id, Some(&playlist_id), None, None)
+        .await
+        .unwrap();
+    spotify
+        .user_playlist(&user_id, None, None, None)
+        .await
+        .unwrap();
+    spotify
+        .playlist_tracks(&playlist_id, None, 10, None, None)
+        .await
+        .unwrap();
+    spotify
+        .user_playlist_create(&user_id, "Mock", true, None)
+        .await
+        .unwrap();
+    spotify
+        .playlist_change_detail(&playlist_id, Some("Renamed"), None, None, None)
+        .await
+        .unwrap();
+    let result = spotify
+        .playlist_add_tracks(&playlist_id, &track_ids, None)
+        .await
+        .unwrap();
+    assert_eq!(result.snapshot_id, "mocksnapshot");
+    spotify
+        .playlist_replace_tracks(&playlist_id, &track_ids)
+        .await
+        .unwrap();
+    spotify
+        .playlist_reorder_tracks(&playlist_id, 0, None, 1, None)
+        .await
+        .unwrap();
+    spotify
+        .playlist_remove_all_occurrences_of_tracks(&playlist_id, &track_ids, None)
+        .await
+        .unwrap();
+    let positions = vec![TrackPositions::new(track_ids[0].clone(), vec![0])];
+    spotify
+        .playlist_remove_specific_occurrences_of_tracks(&playlist_id, &positions, None)
+        .await
+        .unwrap();
+    spotify.playlist_follow(&playlist_id, None).await.unwrap();
+    let follows = spotify
+        .playlist_check_follow(&playlist_id, &[user_id])
+        .await
+        .unwrap();
+    assert_eq!(follows, vec![true]);
+    spotify.playlist_unfollow(&playlist_id).await.unwrap();
+
+    let add = server
+        .requests()
+        .into_iter()
+        .find(|request| request.method == "POST" && request.path.ends_with("/tracks"))
+        .unwrap();
+    assert_eq!(
+        add.json()["uris"],
+        json!([
+            "spotify:track:4iV5W9uYEdYUVa79Axb7Rh",
+            "spotify:track:1301WleyT98MSxVHPZCA6M"
+        ])
+    );
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_playlist_cover_image() {
+    let server = MockServer::start().unwrap();
+    let spotify = mock_client(&server).await;
+    let playlist_id = PlaylistId::from_id("37i9dQZF1DXcBWIGoYBM5M").unwrap();
+
+    let images = spotify.playlist_cover_image(&playlist_id).await.unwrap();
+    assert_eq!(images.len(), 1);
+
+    // The image is sent encoded in base64 instead of JSON
+    spotify
+        .playlist_upload_cover_image(&playlist_id, b"\xff\xd8\xff\xe0mock")
+        .await
+        .unwrap();
+    let upload = server.requests().pop().unwrap();
+    assert_eq!(upload.method, "PUT");
+    assert_eq!(upload.headers["content-type"], "image/jpeg");
+    assert_eq!(upload.body, "/9j/4G1vY2s=");
+
+    // Images that are too large fail before the request
+    server.clear_requests();
+    let image = vec![0; MAX_COVER_IMAGE_SIZE];
+    let result = spotify
+        .playlist_upload_cover_image(&playlist_id, &image)
+        .await;
+    assert!(matches!(result, Err(ClientError::ImageTooLarge(_))));
+    assert!(server.requests().is_empty());
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_library() {
+    let server = MockServer::start().unwrap();
+    let spotify = mock_client(&server).await;
+    let track_ids = track_ids();
+    let album_ids = [AlbumId::from_id("6akEvsycLGftJxYudPjmqK").unwrap()];
+    let artist_ids = [ArtistId::from_id("0OdUWJ0sBjDrqHygGUXeCF").unwrap()];
+    let user_ids = [UserId::from_id("mockuser").unwrap()];
+
+    let me = spotify.me().await.unwrap();
+    assert_eq!(me.id, "mockuser");
+    spotify.current_user().await.unwrap();
+    spotify.current_user_saved_albums(10, 0).await.unwrap();
+    spotify.current_user_saved_tracks(10, 0).await.unwrap();
+    let followed = spotify
+        .current_user_followed_artists(2, None)
+        .await
+        .unwrap();
+    assert_eq!(followed.cursors.after.as_deref(), Some("2"));
+    spotify.current_user_top_artists(10, 0, None).await.unwrap();
+    spotify.current_user_top_tracks(10, 0, None).await.unwrap();
+    let history = spotify.current_user_recently_played(2, None).await.unwrap();
+    assert_eq!(history.cursors.before.as_deref(), Some("2"));
+    let older = spotify
+        .current_user_recently_played(2, history.cursors.before)
+        .await
+        .unwrap();
+    assert_eq!(older.items[0].track.id.as_deref(), Some("mocktrack2"));
+
+    spotify
+        .current_user_saved_tracks_add(&track_ids)
+        .await
+        .unwrap();
+    let contains = spotify
+        .current_user_saved_tracks_contains(&track_ids)
+        .await
+        .unwrap();
+    assert_eq!(contains, vec![true, true]);
+    spotify
+        .current_user_saved_tracks_delete(&track_ids)
+        .await
+        .unwrap();
+    spotify
+        .current_user_saved_albums_add(&album_ids)
+        .await
+        .unwrap();
+    spotify
+        .current_user_saved_albums_contains(&album_ids)
+        .await
+        .unwrap();
+    spotify
+        .current_user_saved_albums_delete(&album_ids)
+        .await
+        .unwrap();
+    spotify.user_follow_artists(&artist_ids).await.unwrap();
+    spotify.user_artist_check_follow(&artist_ids).await.unwrap();
+    spotify.user_unfollow_artists(&artist_ids).await.unwrap();
+    spotify.user_follow_users(&user_ids).await.unwrap();
+    spotify.user_unfollow_users(&user_ids).await.unwrap();
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_browse() {
+    let server = MockServer::start().unwrap();
+    let spotify = mock_client(&server).await;
+    let locale = Locale::new("es", Country::Mexico).unwrap();
+
+    spotify
+        .featured_playlists(Some(locale), None, None, 10, 0)
+        .await
+        .unwrap();
+    spotify.new_releases(None, 10, 0).await.unwrap();
+    let categories = spotify.categories(Some(locale), None, 10, 0).await.unwrap();
+    assert_eq!(categories.items.len() as u32, MOCK_PAGE_TOTAL);
+    let category = spotify
+        .category(&categories.items[0].id, Some(locale), Some(Country::Mexico))
+        .await
+        .unwrap();
+    assert_eq!(category.id, categories.items[0].id);
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.param("locale"), Some("es_MX"));
+    assert_eq!(request.param("country"), Some("MX"));
+    spotify
+        .category_playlists(&categories.items[0].id, None, 10, 0)
+        .await
+        .unwrap();
+
+    let seed_artists = [ArtistId::from_id("0OdUWJ0sBjDrqHygGUXeCF").unwrap()];
+    let recommendations = spotify
+        .recommendations(Some(&seed_artists), None, None, 10, None, &Map::new())
+        .await
+        .unwrap();
+    assert_eq!(recommendations.seeds.len(), 1);
+
+    let genres = spotify.recommendation_genre_seeds().await.unwrap();
+    assert_eq!(genres, vec!["acoustic", "jazz", "rock"]);
+    let markets = spotify.available_markets().await.unwrap();
+    assert_eq!(markets, vec![Country::Spain, Country::UnitedStates]);
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_validate_genre_seeds() {
+    let server = MockServer::start().unwrap();
+    let mut spotify = mock_client(&server).await;
+    spotify.validate_genre_seeds = true;
+    let genres = |genres: &[&str]| Some(genres.iter().map(|&genre| genre.to_owned()).collect());
+
+    spotify
+        .recommendations(None, genres(&["jazz", "rock"]), None, 10, None, &Map::new())
+        .await
+        .unwrap();
+
+    server.clear_requests();
+    let result = spotify
+        .recommendations(
+            None,
+            genres(&["jazz", "vaporwave"]),
+            None,
+            10,
+            None,
+            &Map::new(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(ClientError::UnknownGenreSeeds(unknown)) if unknown == vec!["vaporwave"]
+    ));
+    // Only the available genres were requested
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_player() {
+    let server = MockServer::start().unwrap();
+    let spotify = mock_client(&server).await;
+    let track_ids = track_ids();
+    let device_id = Some("mockdevice".to_owned());
+
+    let devices = spotify.device().await.unwrap();
+    assert_eq!(devices[0].id.as_deref(), Some("mockdevice"));
+    let playback = spotify.current_playback(None, None).await.unwrap();
+    assert!(playback.is_some());
+    let playing = spotify.current_playing(None, None).await.unwrap();
+    assert!(playing.is_some());
+    let playing = spotify.current_user_playing_track().await.unwrap();
+    assert!(playing.is_some());
+
+    spotify.transfer_playback("mockdevice", true).await.unwrap();
+    let uris: Vec<&dyn PlayableId> = track_ids.iter().map(|id| id as &dyn PlayableId).collect();
+    spotify
+        .start_playback(device_id.clone(), None, Some(&uris), for_position(0), None)
+        .await
+        .unwrap();
+    spotify.pause_playback(device_id.clone()).await.unwrap();
+    spotify.next_track(device_id.clone()).await.unwrap();
+    spotify.previous_track(device_id.clone()).await.unwrap();
+    spotify.seek_track(1000, device_id.clone()).await.unwrap();
+    spotify
+        .repeat(RepeatState::Track, device_id.clone())
+        .await
+        .unwrap();
+    spotify.volume(50, device_id.clone()).await.unwrap();
+    spotify.shuffle(true, device_id.clone()).await.unwrap();
+    spotify
+        .add_item_to_queue(&track_ids[0], device_id)
+        .await
+        .unwrap();
+
+    let queue = spotify.current_user_queue().await.unwrap();
+    assert!(matches!(
+        queue.currently_playing,
+        Some(PlayingItem::Track(ref track)) if track.id.as_deref() == Some("mocktrack0")
+    ));
+    assert!(matches!(
+        queue.queue.as_slice(),
+        [PlayingItem::Track(_), PlayingItem::Episode(_)]
+    ));
+
+    let play = server
+        .requests()
+        .into_iter()
+        .find(|request| request.path == "/v1/me/player/play")
+        .unwrap();
+    assert_eq!(play.param("device_id"), Some("mockdevice"));
+    assert_eq!(
+        play.json()["uris"],
+        json!([
+            "spotify:track:4iV5W9uYEdYUVa79Axb7Rh",
+            "spotify:track:1301WleyT98MSxVHPZCA6M"
+        ])
+    );
+}
+
+#[maybe_async]
+#[maybe_async_test]
+async fn test_shows_episodes() {
+    let server = MockServer::start().unwrap();
+    let spotify = mock_client(&server).await;
+    let show_ids = [ShowId::from_id("5CfCWKI5pZ28U0uOzXkDHe").unwrap()];
+    let episode_ids = [EpisodeId::from_id("512ojhOuo1ktJprKbVcKyQ").unwrap()];
+
+    spotify.save_shows(&show_ids).await.unwrap();
+    spotify.get_saved_show(10, 0).await.unwrap();
+    let show = spotify.get_a_show(&show_ids[0], None).await.unwrap();
+    assert_eq!(show.id, show_ids[0].id());
+    spotify.get_several_shows(&show_ids, None).await.unwrap();
+    spotify
+        .get_shows_episodes(&show_ids[0], 10, 0, None)
+        .await
+        .unwrap();
+    spotify.get_an_episode(&episode_ids[0], None).await.unwrap();
+    let episodes = spotify
+        .get_several_episodes(&episode_ids, None)
+        .await
+        .unwrap();
+    assert_eq!(episodes.episodes[0].id, episode_ids[0].id());
+    spotify.check_users_saved_shows(&show_ids).await.unwrap();
+    spotify
+        .remove_users_saved_shows(&show_ids, None)
+        .await
+        .unwrap();
+}
+
+#[cfg(feature = "__async")]
+#[tokio::test]
+async fn test_pagination() {
+    use futures::TryStreamExt;
+
+    let server = MockServer::start().unwrap();
+    let spotify = mock_client(&server).await;
+
+    let tracks: Vec<_> = spotify
+        .current_user_saved_tracks_stream()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(tracks.len() as u32, MOCK_PAGE_TOTAL);
+    let artists: Vec<_> = spotify
+        .current_user_followed_artists_stream()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(artists.len() as u32, MOCK_PAGE_TOTAL);
+    let history: Vec<_> = spotify
+        .current_user_recently_played_stream()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(history.len() as u32, MOCK_PAGE_TOTAL);
+    let playlists: Vec<_> = spotify
+        .featured_playlists_stream(None, None, None)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(playlists.len() as u32, MOCK_PAGE_TOTAL);
+}
+
+#[cfg(feature = "__sync")]
+#[test]
+fn test_pagination() {
+    let server = MockServer::start().unwrap();
+    let spotify = mock_client(&server);
+
+    let tracks: Vec<_> = spotify
+        .current_user_saved_tracks_iter()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(tracks.len() as u32, MOCK_PAGE_TOTAL);
+    let artists: Vec<_> = spotify
+        .current_user_followed_artists_iter()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(artists.len() as u32, MOCK_PAGE_TOTAL);
+    let history: Vec<_> = spotify
+        .current_user_recently_played_iter()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(history.len() as u32, MOCK_PAGE_TOTAL);
+    let playlists: Vec<_> = spotify
+        .featured_playlists_iter(None, None, None)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(playlists.len() as u32, MOCK_PAGE_TOTAL);
+}
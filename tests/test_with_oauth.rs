@@ -19,8 +19,12 @@ mod common;
 use common::maybe_async_test;
 use rspotify::client::{Spotify, SpotifyBuilder};
 use rspotify::model::offset::for_position;
-use rspotify::model::{Country, RepeatState, SearchType, TimeRange};
+use rspotify::model::{
+    AlbumId, ArtistId, Country, PlayableId, PlaylistId, RepeatState, SearchType, ShowId, TimeRange,
+    TrackId, TrackPositions, UserId,
+};
 use rspotify::oauth2::{CredentialsBuilder, OAuthBuilder, TokenBuilder};
+use rspotify::scopes::Scopes;
 
 use std::env;
 
@@ -45,15 +49,7 @@ pub async fn oauth_client() -> Spotify {
 
         // Using every possible scope
         let oauth = OAuthBuilder::from_env()
-            .scope(
-                "user-read-email user-read-private user-top-read \
-                 user-read-recently-played user-follow-read user-library-read \
-                 user-read-currently-playing user-read-playback-state \
-                 user-read-playback-position playlist-read-collaborative \
-                 playlist-read-private user-follow-modify user-library-modify \
-                 user-modify-playback-state playlist-modify-public \
-                 playlist-modify-private ugc-image-upload",
-            )
+            .scope(Scopes::all())
             .build()
             .unwrap();
 
@@ -158,7 +154,7 @@ async fn test_current_user_playlists() {
 async fn test_current_user_recently_played() {
     oauth_client()
         .await
-        .current_user_recently_played(10)
+        .current_user_recently_played(10, None)
         .await
         .unwrap();
 }
@@ -167,14 +163,13 @@ async fn test_current_user_recently_played() {
 #[maybe_async_test]
 #[ignore]
 async fn test_current_user_saved_albums_add() {
-    let mut album_ids = vec![];
-    let album_id1 = "6akEvsycLGftJxYudPjmqK";
-    let album_id2 = "628oezqK2qfmCjC6eXNors";
-    album_ids.push(album_id2);
-    album_ids.push(album_id1);
+    let album_ids = vec![
+        AlbumId::from_id("628oezqK2qfmCjC6eXNors").unwrap(),
+        AlbumId::from_id("6akEvsycLGftJxYudPjmqK").unwrap(),
+    ];
     oauth_client()
         .await
-        .current_user_saved_albums_add(album_ids)
+        .current_user_saved_albums_add(&album_ids)
         .await
         .unwrap();
 }
@@ -183,14 +178,13 @@ async fn test_current_user_saved_albums_add() {
 #[maybe_async_test]
 #[ignore]
 async fn test_current_user_saved_albums_delete() {
-    let mut album_ids = vec![];
-    let album_id1 = "6akEvsycLGftJxYudPjmqK";
-    let album_id2 = "628oezqK2qfmCjC6eXNors";
-    album_ids.push(album_id2);
-    album_ids.push(album_id1);
+    let album_ids = vec![
+        AlbumId::from_id("628oezqK2qfmCjC6eXNors").unwrap(),
+        AlbumId::from_id("6akEvsycLGftJxYudPjmqK").unwrap(),
+    ];
     oauth_client()
         .await
-        .current_user_saved_albums_delete(album_ids)
+        .current_user_saved_albums_delete(&album_ids)
         .await
         .unwrap();
 }
@@ -210,14 +204,13 @@ async fn test_current_user_saved_albums() {
 #[maybe_async_test]
 #[ignore]
 async fn test_current_user_saved_tracks_add() {
-    let mut tracks_ids = vec![];
-    let track_id1 = "spotify:track:4iV5W9uYEdYUVa79Axb7Rh";
-    let track_id2 = "spotify:track:1301WleyT98MSxVHPZCA6M";
-    tracks_ids.push(track_id2);
-    tracks_ids.push(track_id1);
+    let tracks_ids = vec![
+        TrackId::from_uri("spotify:track:1301WleyT98MSxVHPZCA6M").unwrap(),
+        TrackId::from_uri("spotify:track:4iV5W9uYEdYUVa79Axb7Rh").unwrap(),
+    ];
     oauth_client()
         .await
-        .current_user_saved_tracks_add(tracks_ids)
+        .current_user_saved_tracks_add(&tracks_ids)
         .await
         .unwrap();
 }
@@ -226,14 +219,13 @@ async fn test_current_user_saved_tracks_add() {
 #[maybe_async_test]
 #[ignore]
 async fn test_current_user_saved_tracks_contains() {
-    let mut tracks_ids = vec![];
-    let track_id1 = "spotify:track:4iV5W9uYEdYUVa79Axb7Rh";
-    let track_id2 = "spotify:track:1301WleyT98MSxVHPZCA6M";
-    tracks_ids.push(track_id2);
-    tracks_ids.push(track_id1);
+    let tracks_ids = vec![
+        TrackId::from_uri("spotify:track:1301WleyT98MSxVHPZCA6M").unwrap(),
+        TrackId::from_uri("spotify:track:4iV5W9uYEdYUVa79Axb7Rh").unwrap(),
+    ];
     oauth_client()
         .await
-        .current_user_saved_tracks_contains(tracks_ids)
+        .current_user_saved_tracks_contains(&tracks_ids)
         .await
         .unwrap();
 }
@@ -242,14 +234,13 @@ async fn test_current_user_saved_tracks_contains() {
 #[maybe_async_test]
 #[ignore]
 async fn test_current_user_saved_tracks_delete() {
-    let mut tracks_ids = vec![];
-    let track_id1 = "spotify:track:4iV5W9uYEdYUVa79Axb7Rh";
-    let track_id2 = "spotify:track:1301WleyT98MSxVHPZCA6M";
-    tracks_ids.push(track_id2);
-    tracks_ids.push(track_id1);
+    let tracks_ids = vec![
+        TrackId::from_uri("spotify:track:1301WleyT98MSxVHPZCA6M").unwrap(),
+        TrackId::from_uri("spotify:track:4iV5W9uYEdYUVa79Axb7Rh").unwrap(),
+    ];
     oauth_client()
         .await
-        .current_user_saved_tracks_delete(tracks_ids)
+        .current_user_saved_tracks_delete(&tracks_ids)
         .await
         .unwrap();
 }
@@ -365,16 +356,16 @@ async fn test_previous_playback() {
 #[ignore]
 async fn test_recommendations() {
     let mut payload = Map::new();
-    let seed_artists = vec!["4NHQUGzhtTLFvgF5SZesLK".to_owned()];
-    let seed_tracks = vec!["0c6xIDDpzE81m2q797ordA".to_owned()];
+    let seed_artists = vec![ArtistId::from_id("4NHQUGzhtTLFvgF5SZesLK").unwrap()];
+    let seed_tracks = vec![TrackId::from_id("0c6xIDDpzE81m2q797ordA").unwrap()];
     payload.insert("min_energy".to_owned(), 0.4.into());
     payload.insert("min_popularity".to_owned(), 50.into());
     oauth_client()
         .await
         .recommendations(
-            Some(seed_artists),
+            Some(&seed_artists),
             None,
-            Some(seed_tracks),
+            Some(&seed_tracks),
             10,
             Some(Country::UnitedStates),
             &payload,
@@ -482,10 +473,11 @@ async fn test_shuffle() {
 #[ignore]
 async fn test_start_playback() {
     let device_id = String::from("74ASZWbe4lXaubB36ztrGX");
-    let uris = vec!["spotify:track:4iV5W9uYEdYUVa79Axb7Rh".to_owned()];
+    let track = TrackId::from_uri("spotify:track:4iV5W9uYEdYUVa79Axb7Rh").unwrap();
+    let uris: [&dyn PlayableId; 1] = [&track];
     oauth_client()
         .await
-        .start_playback(Some(device_id), None, Some(uris), for_position(0), None)
+        .start_playback(Some(device_id), None, Some(&uris), for_position(0), None)
         .await
         .unwrap();
 }
@@ -506,14 +498,13 @@ async fn test_transfer_playback() {
 #[maybe_async_test]
 #[ignore]
 async fn test_user_follow_artist() {
-    let mut artists = vec![];
-    let artist_id1 = "74ASZWbe4lXaubB36ztrGX";
-    let artist_id2 = "08td7MxkoHQkXnWAYD8d6Q";
-    artists.push(artist_id2);
-    artists.push(artist_id1);
+    let artists = vec![
+        ArtistId::from_id("08td7MxkoHQkXnWAYD8d6Q").unwrap(),
+        ArtistId::from_id("74ASZWbe4lXaubB36ztrGX").unwrap(),
+    ];
     oauth_client()
         .await
-        .user_follow_artists(artists)
+        .user_follow_artists(&artists)
         .await
         .unwrap();
 }
@@ -522,14 +513,13 @@ async fn test_user_follow_artist() {
 #[maybe_async_test]
 #[ignore]
 async fn test_user_unfollow_artist() {
-    let mut artists = vec![];
-    let artist_id1 = "74ASZWbe4lXaubB36ztrGX";
-    let artist_id2 = "08td7MxkoHQkXnWAYD8d6Q";
-    artists.push(artist_id2);
-    artists.push(artist_id1);
+    let artists = vec![
+        ArtistId::from_id("08td7MxkoHQkXnWAYD8d6Q").unwrap(),
+        ArtistId::from_id("74ASZWbe4lXaubB36ztrGX").unwrap(),
+    ];
     oauth_client()
         .await
-        .user_unfollow_artists(artists)
+        .user_unfollow_artists(&artists)
         .await
         .unwrap();
 }
@@ -538,22 +528,22 @@ async fn test_user_unfollow_artist() {
 #[maybe_async_test]
 #[ignore]
 async fn test_user_follow_users() {
-    let mut users = vec![];
-    let user_id1 = "exampleuser01";
-    users.push(user_id1);
-    oauth_client().await.user_follow_users(users).await.unwrap();
+    let users = vec![UserId::from_id("exampleuser01").unwrap()];
+    oauth_client()
+        .await
+        .user_follow_users(&users)
+        .await
+        .unwrap();
 }
 
 #[maybe_async]
 #[maybe_async_test]
 #[ignore]
 async fn test_user_unfollow_users() {
-    let mut users = vec![];
-    let user_id1 = "exampleuser01";
-    users.push(user_id1);
+    let users = vec![UserId::from_id("exampleuser01").unwrap()];
     oauth_client()
         .await
-        .user_unfollow_users(users)
+        .user_unfollow_users(&users)
         .await
         .unwrap();
 }
@@ -562,15 +552,14 @@ async fn test_user_unfollow_users() {
 #[maybe_async_test]
 #[ignore]
 async fn test_playlist_add_tracks() {
-    let playlist_id = "5jAOgWXCBKuinsGiZxjDQ5";
-    let mut tracks_ids = vec![];
-    let track_id1 = "spotify:track:4iV5W9uYEdYUVa79Axb7Rh";
-    tracks_ids.push(track_id1);
-    let track_id2 = "spotify:track:1301WleyT98MSxVHPZCA6M";
-    tracks_ids.push(track_id2);
+    let playlist_id = PlaylistId::from_id("5jAOgWXCBKuinsGiZxjDQ5").unwrap();
+    let tracks_ids = vec![
+        TrackId::from_uri("spotify:track:4iV5W9uYEdYUVa79Axb7Rh").unwrap(),
+        TrackId::from_uri("spotify:track:1301WleyT98MSxVHPZCA6M").unwrap(),
+    ];
     oauth_client()
         .await
-        .playlist_add_tracks(playlist_id, tracks_ids, None)
+        .playlist_add_tracks(&playlist_id, &tracks_ids, None)
         .await
         .unwrap();
 }
@@ -579,11 +568,11 @@ async fn test_playlist_add_tracks() {
 #[maybe_async_test]
 #[ignore]
 async fn test_playlist_change_detail() {
-    let playlist_id = "5jAOgWXCBKuinsGiZxjDQ5";
+    let playlist_id = PlaylistId::from_id("5jAOgWXCBKuinsGiZxjDQ5").unwrap();
     let playlist_name = "A New Playlist-update";
     oauth_client()
         .await
-        .playlist_change_detail(playlist_id, Some(playlist_name), Some(false), None, None)
+        .playlist_change_detail(&playlist_id, Some(playlist_name), Some(false), None, None)
         .await
         .unwrap();
 }
@@ -592,15 +581,14 @@ async fn test_playlist_change_detail() {
 #[maybe_async_test]
 #[ignore]
 async fn test_playlist_check_follow() {
-    let playlist_id = "2v3iNvBX8Ay1Gt2uXtUKUT";
-    let mut user_ids: Vec<String> = vec![];
-    let user_id1 = String::from("possan");
-    user_ids.push(user_id1);
-    let user_id2 = String::from("elogain");
-    user_ids.push(user_id2);
+    let playlist_id = PlaylistId::from_id("2v3iNvBX8Ay1Gt2uXtUKUT").unwrap();
+    let user_ids = vec![
+        UserId::from_id("possan").unwrap(),
+        UserId::from_id("elogain").unwrap(),
+    ];
     oauth_client()
         .await
-        .playlist_check_follow(playlist_id, &user_ids)
+        .playlist_check_follow(&playlist_id, &user_ids)
         .await
         .unwrap();
 }
@@ -609,11 +597,11 @@ async fn test_playlist_check_follow() {
 #[maybe_async_test]
 #[ignore]
 async fn test_user_playlist_create() {
-    let user_id = "2257tjys2e2u2ygfke42niy2q";
+    let user_id = UserId::from_id("2257tjys2e2u2ygfke42niy2q").unwrap();
     let playlist_name = "A New Playlist";
     oauth_client()
         .await
-        .user_playlist_create(user_id, playlist_name, false, None)
+        .user_playlist_create(&user_id, playlist_name, false, None)
         .await
         .unwrap();
 }
@@ -622,10 +610,10 @@ async fn test_user_playlist_create() {
 #[maybe_async_test]
 #[ignore]
 async fn test_playlist_follow_playlist() {
-    let playlist_id = "2v3iNvBX8Ay1Gt2uXtUKUT";
+    let playlist_id = PlaylistId::from_id("2v3iNvBX8Ay1Gt2uXtUKUT").unwrap();
     oauth_client()
         .await
-        .playlist_follow(playlist_id, true)
+        .playlist_follow(&playlist_id, true)
         .await
         .unwrap();
 }
@@ -634,13 +622,13 @@ async fn test_playlist_follow_playlist() {
 #[maybe_async_test]
 #[ignore]
 async fn test_playlist_recorder_tracks() {
-    let playlist_id = "5jAOgWXCBKuinsGiZxjDQ5";
+    let playlist_id = PlaylistId::from_id("5jAOgWXCBKuinsGiZxjDQ5").unwrap();
     let range_start = 0;
     let insert_before = 1;
     let range_length = 1;
     oauth_client()
         .await
-        .playlist_reorder_tracks(playlist_id, range_start, range_length, insert_before, None)
+        .playlist_reorder_tracks(&playlist_id, range_start, range_length, insert_before, None)
         .await
         .unwrap();
 }
@@ -649,15 +637,14 @@ async fn test_playlist_recorder_tracks() {
 #[maybe_async_test]
 #[ignore]
 async fn test_playlist_remove_all_occurrences_of_tracks() {
-    let playlist_id = "5jAOgWXCBKuinsGiZxjDQ5";
-    let mut tracks_ids = vec![];
-    let track_id1 = "spotify:track:4iV5W9uYEdYUVa79Axb7Rh";
-    let track_id2 = "spotify:track:1301WleyT98MSxVHPZCA6M";
-    tracks_ids.push(track_id2);
-    tracks_ids.push(track_id1);
+    let playlist_id = PlaylistId::from_id("5jAOgWXCBKuinsGiZxjDQ5").unwrap();
+    let tracks_ids = vec![
+        TrackId::from_uri("spotify:track:1301WleyT98MSxVHPZCA6M").unwrap(),
+        TrackId::from_uri("spotify:track:4iV5W9uYEdYUVa79Axb7Rh").unwrap(),
+    ];
     oauth_client()
         .await
-        .playlist_remove_all_occurrences_of_tracks(playlist_id, tracks_ids, None)
+        .playlist_remove_all_occurrences_of_tracks(&playlist_id, &tracks_ids, None)
         .await
         .unwrap();
 }
@@ -666,30 +653,20 @@ async fn test_playlist_remove_all_occurrences_of_tracks() {
 #[maybe_async_test]
 #[ignore]
 async fn test_playlist_remove_specific_occurrences_of_tracks() {
-    let playlist_id = String::from("5jAOgWXCBKuinsGiZxjDQ5");
-    let mut tracks = vec![];
-    let mut map1 = Map::new();
-    let mut position1 = vec![];
-    position1.push(0);
-    position1.push(3);
-    map1.insert(
-        "uri".to_string(),
-        "spotify:track:4iV5W9uYEdYUVa79Axb7Rh".into(),
-    );
-    map1.insert("position".to_string(), position1.into());
-    tracks.push(map1);
-    let mut map2 = Map::new();
-    let mut position2 = vec![];
-    position2.push(7);
-    map2.insert(
-        "uri".to_string(),
-        "spotify:track:1301WleyT98MSxVHPZCA6M".into(),
-    );
-    map2.insert("position".to_string(), position2.into());
-    tracks.push(map2);
+    let playlist_id = PlaylistId::from_id("5jAOgWXCBKuinsGiZxjDQ5").unwrap();
+    let tracks = vec![
+        TrackPositions::new(
+            TrackId::from_uri("spotify:track:4iV5W9uYEdYUVa79Axb7Rh").unwrap(),
+            vec![0, 3],
+        ),
+        TrackPositions::new(
+            TrackId::from_uri("spotify:track:1301WleyT98MSxVHPZCA6M").unwrap(),
+            vec![7],
+        ),
+    ];
     oauth_client()
         .await
-        .playlist_remove_specific_occurrences_of_tracks(&playlist_id, tracks, None)
+        .playlist_remove_specific_occurrences_of_tracks(&playlist_id, &tracks, None)
         .await
         .unwrap();
 }
@@ -698,15 +675,14 @@ async fn test_playlist_remove_specific_occurrences_of_tracks() {
 #[maybe_async_test]
 #[ignore]
 async fn test_playlist_replace_tracks() {
-    let playlist_id = "5jAOgWXCBKuinsGiZxjDQ5";
-    let mut tracks_ids = vec![];
-    let track_id1 = "spotify:track:4iV5W9uYEdYUVa79Axb7Rh";
-    let track_id2 = "spotify:track:1301WleyT98MSxVHPZCA6M";
-    tracks_ids.push(track_id2);
-    tracks_ids.push(track_id1);
+    let playlist_id = PlaylistId::from_id("5jAOgWXCBKuinsGiZxjDQ5").unwrap();
+    let tracks_ids = vec![
+        TrackId::from_uri("spotify:track:1301WleyT98MSxVHPZCA6M").unwrap(),
+        TrackId::from_uri("spotify:track:4iV5W9uYEdYUVa79Axb7Rh").unwrap(),
+    ];
     oauth_client()
         .await
-        .playlist_replace_tracks(playlist_id, tracks_ids)
+        .playlist_replace_tracks(&playlist_id, &tracks_ids)
         .await
         .unwrap();
 }
@@ -715,11 +691,11 @@ async fn test_playlist_replace_tracks() {
 #[maybe_async_test]
 #[ignore]
 async fn test_user_playlist() {
-    let user_id = "spotify";
-    let mut playlist_id = String::from("59ZbFPES4DQwEjBpWHzrtC");
+    let user_id = UserId::from_id("spotify").unwrap();
+    let playlist_id = PlaylistId::from_id("59ZbFPES4DQwEjBpWHzrtC").unwrap();
     oauth_client()
         .await
-        .user_playlist(user_id, Some(&mut playlist_id), None, None)
+        .user_playlist(&user_id, Some(&playlist_id), None, None)
         .await
         .unwrap();
 }
@@ -728,10 +704,10 @@ async fn test_user_playlist() {
 #[maybe_async_test]
 #[ignore]
 async fn test_user_playlists() {
-    let user_id = "2257tjys2e2u2ygfke42niy2q";
+    let user_id = UserId::from_id("2257tjys2e2u2ygfke42niy2q").unwrap();
     oauth_client()
         .await
-        .user_playlists(user_id, Some(10), None)
+        .user_playlists(&user_id, Some(10), None)
         .await
         .unwrap();
 }
@@ -740,7 +716,7 @@ async fn test_user_playlists() {
 #[maybe_async_test]
 #[ignore]
 async fn test_playlist_tracks() {
-    let playlist_id = String::from("spotify:playlist:59ZbFPES4DQwEjBpWHzrtC");
+    let playlist_id = PlaylistId::from_uri("spotify:playlist:59ZbFPES4DQwEjBpWHzrtC").unwrap();
     oauth_client()
         .await
         .playlist_tracks(&playlist_id, None, Some(2), None, None)
@@ -752,10 +728,10 @@ async fn test_playlist_tracks() {
 #[maybe_async_test]
 #[ignore]
 async fn test_playlist_unfollow() {
-    let playlist_id = "65V6djkcVRyOStLd8nza8E";
+    let playlist_id = PlaylistId::from_id("65V6djkcVRyOStLd8nza8E").unwrap();
     oauth_client()
         .await
-        .playlist_unfollow(playlist_id)
+        .playlist_unfollow(&playlist_id)
         .await
         .unwrap();
 }
@@ -771,10 +747,10 @@ async fn test_volume() {
 #[maybe_async_test]
 #[ignore]
 async fn test_add_queue() {
-    let birdy_uri = String::from("spotify:track:6rqhFgbbKwnb9MLmUQDhG6");
+    let birdy_uri = TrackId::from_uri("spotify:track:6rqhFgbbKwnb9MLmUQDhG6").unwrap();
     oauth_client()
         .await
-        .add_item_to_queue(birdy_uri, None)
+        .add_item_to_queue(&birdy_uri, None)
         .await
         .unwrap();
 }
@@ -783,12 +759,13 @@ async fn test_add_queue() {
 #[maybe_async_test]
 #[ignore]
 async fn test_get_several_shows() {
+    let show_ids = vec![
+        ShowId::from_id("5CfCWKI5pZ28U0uOzXkDHe").unwrap(),
+        ShowId::from_id("5as3aKmN2k11yfDDDSrvaZ").unwrap(),
+    ];
     oauth_client()
         .await
-        .get_several_shows(
-            vec!["5CfCWKI5pZ28U0uOzXkDHe", "5as3aKmN2k11yfDDDSrvaZ"],
-            None,
-        )
+        .get_several_shows(&show_ids, None)
         .await
         .unwrap();
 }